@@ -0,0 +1,348 @@
+// Résolution des attaques en combat : précision, esquive, coups critiques et variance des dégâts
+
+use rand::Rng;
+
+use std::fmt;
+
+use crate::modele::{Attaque, Comportement, Element};
+
+// Chance de base qu'une attaque atteigne sa cible
+pub const CHANCE_TOUCHER: f64 = 0.9;
+
+// Chance de base d'esquiver, augmentée d'un point par point de puissance d'avance du défenseur
+pub const CHANCE_ESQUIVE_BASE: f64 = 0.05;
+pub const CHANCE_ESQUIVE_PAR_POINT: f64 = 0.01;
+pub const CHANCE_ESQUIVE_MAX: f64 = 0.35;
+
+// Coups critiques : chance et multiplicateur des dégâts
+pub const CHANCE_CRITIQUE: f64 = 0.1;
+pub const MULTIPLICATEUR_CRITIQUE: f64 = 1.5;
+
+// Les dégâts varient de ±15 % autour de la valeur de base
+pub const VARIANCE_DEGATS: f64 = 0.15;
+
+// Chance de réussir à fuir un combat
+pub const CHANCE_FUITE: f64 = 0.6;
+
+// Énergie (haki) du joueur : réserve maximale et récupération à chaque tour
+pub const ENERGIE_MAX: u32 = 100;
+pub const REGENERATION_ENERGIE: u32 = 15;
+
+// Part des dégâts évitée en se défendant
+pub const REDUCTION_DEFENSE: f64 = 0.5;
+
+// Action choisie par le joueur à son tour de combat
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ActionCombat {
+    // Index de l'attaque choisie (0 pour l'attaque normale)
+    Attaquer(usize),
+    Defendre,
+    Manger,
+    Fuir,
+}
+
+// Interpréter la saisie du joueur : un numéro d'attaque (1 à nombre_attaques) ou D, M, F
+pub fn lire_action(saisie: &str, nombre_attaques: usize) -> Option<ActionCombat> {
+    let saisie = saisie.trim();
+    match saisie.to_uppercase().as_str() {
+        "D" => Some(ActionCombat::Defendre),
+        "M" => Some(ActionCombat::Manger),
+        "F" => Some(ActionCombat::Fuir),
+        _ => match saisie.parse::<usize>() {
+            Ok(num) if num > 0 && num <= nombre_attaques.max(1) => Some(ActionCombat::Attaquer(num - 1)),
+            _ => None,
+        },
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ResultatAttaque {
+    Ratee,
+    Esquivee,
+    Touchee { degats: u32, critique: bool },
+}
+
+impl ResultatAttaque {
+    pub fn degats(&self) -> u32 {
+        match self {
+            ResultatAttaque::Touchee { degats, .. } => *degats,
+            ResultatAttaque::Ratee | ResultatAttaque::Esquivee => 0,
+        }
+    }
+
+    // Le même résultat avec des dégâts multipliés, au moins 1 point
+    pub fn multiplier(self, facteur: f64) -> ResultatAttaque {
+        match self {
+            ResultatAttaque::Touchee { degats, critique } => {
+                let degats = ((degats as f64 * facteur).round() as u32).max(1);
+                ResultatAttaque::Touchee { degats, critique }
+            }
+            autre => autre,
+        }
+    }
+
+    // Le même résultat contre un défenseur en garde : dégâts réduits
+    pub fn en_defense(self) -> ResultatAttaque {
+        self.multiplier(1.0 - REDUCTION_DEFENSE)
+    }
+
+    // Le même résultat amorti par l'équipement du défenseur : dégâts diminués de sa défense, au moins 1 point
+    pub fn amortir(self, defense: u32) -> ResultatAttaque {
+        match self {
+            ResultatAttaque::Touchee { degats, critique } => {
+                ResultatAttaque::Touchee { degats: degats.saturating_sub(defense).max(1), critique }
+            }
+            autre => autre,
+        }
+    }
+}
+
+// Chance que le défenseur esquive, selon l'écart de puissance avec l'attaquant
+pub fn chance_esquive(puissance_attaquant: u32, puissance_defenseur: u32) -> f64 {
+    let ecart = puissance_defenseur as f64 - puissance_attaquant as f64;
+    (CHANCE_ESQUIVE_BASE + ecart * CHANCE_ESQUIVE_PAR_POINT).clamp(0.0, CHANCE_ESQUIVE_MAX)
+}
+
+// Résoudre une attaque : les dégâts de base sont la puissance de l'attaquant plus celle de l'attaque
+pub fn resoudre_attaque(
+    rng: &mut impl Rng,
+    puissance_attaquant: u32,
+    puissance_defenseur: u32,
+    puissance_attaque: u32,
+) -> ResultatAttaque {
+    if !rng.random_bool(CHANCE_TOUCHER) {
+        return ResultatAttaque::Ratee;
+    }
+    if rng.random_bool(chance_esquive(puissance_attaquant, puissance_defenseur)) {
+        return ResultatAttaque::Esquivee;
+    }
+
+    let base = (puissance_attaquant + puissance_attaque) as f64;
+    let variance = rng.random_range(1.0 - VARIANCE_DEGATS..=1.0 + VARIANCE_DEGATS);
+    let critique = rng.random_bool(CHANCE_CRITIQUE);
+    let multiplicateur = if critique { MULTIPLICATEUR_CRITIQUE } else { 1.0 };
+
+    // Une attaque qui touche inflige toujours au moins 1 point de dégâts
+    let degats = ((base * variance * multiplicateur).round() as u32).max(1);
+    ResultatAttaque::Touchee { degats, critique }
+}
+
+impl fmt::Display for Element {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let nom = match self {
+            Element::Logia => "Logia",
+            Element::Paramecia => "Paramecia",
+            Element::Zoan => "Zoan",
+            Element::Eau => "Eau",
+            Element::Feu => "Feu",
+            Element::Haki => "Haki",
+        };
+        write!(f, "{}", nom)
+    }
+}
+
+// Éléments entre crochets pour l'affichage (" [Logia, Feu]"), rien s'il n'y en a pas
+pub fn etiquette_elements(elements: &[Element]) -> String {
+    if elements.is_empty() {
+        return String::new();
+    }
+    let noms: Vec<String> = elements.iter().map(|e| e.to_string()).collect();
+    format!(" [{}]", noms.join(", "))
+}
+
+// Table des faiblesses : multiplicateur des dégâts d'un élément d'attaque contre un élément du défenseur.
+// Le haki et l'eau touchent les Logia ; Logia, Paramecia et Zoan forment un cycle.
+pub fn multiplicateur_element(attaque: Element, defenseur: Element) -> f64 {
+    use Element::*;
+    match (attaque, defenseur) {
+        (Eau, Logia) => 2.0,
+        (Haki, Logia) | (Eau, Feu) => 1.5,
+        (Logia, Paramecia) | (Paramecia, Zoan) | (Zoan, Logia) => 1.25,
+        (Paramecia, Logia) | (Zoan, Paramecia) | (Logia, Zoan) => 0.8,
+        (Feu, Feu) => 0.5,
+        _ => 1.0,
+    }
+}
+
+// Multiplicateur total d'une attaque : produit sur toutes les paires d'éléments
+pub fn multiplicateur_elements(attaque: &[Element], defenseur: &[Element]) -> f64 {
+    attaque
+        .iter()
+        .flat_map(|a| defenseur.iter().map(move |d| multiplicateur_element(*a, *d)))
+        .product()
+}
+
+// Choisir l'attaque d'un ennemi selon son comportement.
+// `tour` compte les tours de combat à partir de 0 ; renvoie None si l'ennemi n'a aucune attaque.
+pub fn choisir_attaque_ennemi<'a>(
+    rng: &mut impl Rng,
+    comportement: &Comportement,
+    attaques: &'a [Attaque],
+    hp: u32,
+    tour: usize,
+) -> Option<&'a Attaque> {
+    if attaques.is_empty() {
+        return None;
+    }
+    match comportement {
+        Comportement::Premiere => attaques.first(),
+        Comportement::Aleatoire => Some(&attaques[rng.random_range(0..attaques.len())]),
+        Comportement::Pondere { poids } => {
+            let total: u32 = (0..attaques.len()).map(|i| poids.get(i).copied().unwrap_or(0)).sum();
+            if total == 0 {
+                return Some(&attaques[rng.random_range(0..attaques.len())]);
+            }
+            let mut tirage = rng.random_range(0..total);
+            for (i, attaque) in attaques.iter().enumerate() {
+                let poids = poids.get(i).copied().unwrap_or(0);
+                if tirage < poids {
+                    return Some(attaque);
+                }
+                tirage -= poids;
+            }
+            attaques.last()
+        }
+        Comportement::Desespere { seuil_hp } => {
+            if hp <= *seuil_hp {
+                attaques.iter().max_by_key(|a| a.puissance)
+            } else {
+                Some(&attaques[rng.random_range(0..attaques.len())])
+            }
+        }
+        Comportement::Finisseur { motif } => {
+            if motif.is_empty() {
+                return attaques.first();
+            }
+            let id = &motif[tour % motif.len()];
+            attaques.iter().find(|a| &a.id == id).or(attaques.first())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn attaque(id: &str, puissance: u32) -> Attaque {
+        Attaque { id: id.to_string(), nom: id.to_string(), description: String::new(), puissance, cout: 0, effet: None, elements: vec![] }
+    }
+
+    #[test]
+    fn test_chance_esquive_selon_ecart_de_puissance() {
+        assert_eq!(chance_esquive(10, 10), CHANCE_ESQUIVE_BASE);
+        assert_eq!(chance_esquive(100, 10), 0.0);
+        assert_eq!(chance_esquive(10, 500), CHANCE_ESQUIVE_MAX);
+        assert!(chance_esquive(10, 20) > chance_esquive(10, 15));
+    }
+
+    #[test]
+    fn test_degats_dans_les_bornes() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let (mut touches, mut critiques) = (0, 0);
+        for _ in 0..1000 {
+            if let ResultatAttaque::Touchee { degats, critique } = resoudre_attaque(&mut rng, 10, 10, 30) {
+                touches += 1;
+                let max = if critique {
+                    critiques += 1;
+                    40.0 * (1.0 + VARIANCE_DEGATS) * MULTIPLICATEUR_CRITIQUE
+                } else {
+                    40.0 * (1.0 + VARIANCE_DEGATS)
+                };
+                assert!(degats as f64 >= (40.0 * (1.0 - VARIANCE_DEGATS)).floor());
+                assert!(degats as f64 <= max.ceil());
+            }
+        }
+        // Environ 85 % des attaques touchent, dont environ 10 % de critiques
+        assert!((750..950).contains(&touches), "{}", touches);
+        assert!(critiques > 0 && critiques < touches / 4);
+    }
+
+    #[test]
+    fn test_resolution_reproductible() {
+        let tirages = |graine| {
+            let mut rng = StdRng::seed_from_u64(graine);
+            (0..20).map(|_| resoudre_attaque(&mut rng, 15, 30, 20)).collect::<Vec<_>>()
+        };
+        assert_eq!(tirages(9), tirages(9));
+    }
+
+    #[test]
+    fn test_lire_action() {
+        assert_eq!(lire_action("2", 3), Some(ActionCombat::Attaquer(1)));
+        assert_eq!(lire_action(" d ", 3), Some(ActionCombat::Defendre));
+        assert_eq!(lire_action("M", 3), Some(ActionCombat::Manger));
+        assert_eq!(lire_action("f", 3), Some(ActionCombat::Fuir));
+        // Sans attaque spéciale, seule l'attaque normale (1) est proposée
+        assert_eq!(lire_action("1", 0), Some(ActionCombat::Attaquer(0)));
+        assert_eq!(lire_action("2", 0), None);
+        assert_eq!(lire_action("0", 3), None);
+        assert_eq!(lire_action("", 3), None);
+        assert_eq!(lire_action("attaque", 3), None);
+    }
+
+    #[test]
+    fn test_defense_reduit_les_degats() {
+        assert_eq!(ResultatAttaque::Touchee { degats: 40, critique: true }.en_defense(), ResultatAttaque::Touchee { degats: 20, critique: true });
+        assert_eq!(ResultatAttaque::Touchee { degats: 1, critique: false }.en_defense().degats(), 1);
+        assert_eq!(ResultatAttaque::Esquivee.en_defense(), ResultatAttaque::Esquivee);
+        assert_eq!(ResultatAttaque::Touchee { degats: 40, critique: false }.amortir(15).degats(), 25);
+        assert_eq!(ResultatAttaque::Touchee { degats: 10, critique: false }.amortir(15).degats(), 1);
+    }
+
+    #[test]
+    fn test_multiplicateur_elements() {
+        use Element::*;
+        assert_eq!(multiplicateur_elements(&[Eau], &[Logia]), 2.0);
+        assert_eq!(multiplicateur_elements(&[Paramecia, Haki], &[Logia]), 0.8 * 1.5);
+        assert_eq!(multiplicateur_elements(&[Logia, Feu], &[Logia, Feu]), 0.5);
+        // Sans élément d'un côté ou de l'autre, les dégâts sont inchangés
+        assert_eq!(multiplicateur_elements(&[], &[Logia]), 1.0);
+        assert_eq!(multiplicateur_elements(&[Feu], &[]), 1.0);
+
+        let touche = ResultatAttaque::Touchee { degats: 30, critique: false };
+        assert_eq!(touche.multiplier(multiplicateur_elements(&[Eau], &[Logia])).degats(), 60);
+        assert_eq!(ResultatAttaque::Ratee.multiplier(2.0), ResultatAttaque::Ratee);
+        assert_eq!(etiquette_elements(&[Logia, Feu]), " [Logia, Feu]");
+        assert_eq!(etiquette_elements(&[]), "");
+    }
+
+    #[test]
+    fn test_comportements_des_ennemis() {
+        let attaques = vec![attaque("griffe", 10), attaque("sable", 30), attaque("tempete", 20)];
+        let mut rng = StdRng::seed_from_u64(3);
+        let ids = |rng: &mut StdRng, comportement: &Comportement, hp: u32| {
+            (0..6)
+                .map(|tour| choisir_attaque_ennemi(rng, comportement, &attaques, hp, tour).unwrap().id.clone())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(ids(&mut rng, &Comportement::Premiere, 100), vec!["griffe"; 6]);
+
+        let pondere = Comportement::Pondere { poids: vec![0, 1, 0] };
+        assert_eq!(ids(&mut rng, &pondere, 100), vec!["sable"; 6]);
+
+        let desespere = Comportement::Desespere { seuil_hp: 30 };
+        assert_eq!(ids(&mut rng, &desespere, 25), vec!["sable"; 6]);
+
+        let finisseur = Comportement::Finisseur { motif: vec!["griffe".to_string(), "griffe".to_string(), "tempete".to_string()] };
+        assert_eq!(ids(&mut rng, &finisseur, 100), vec!["griffe", "griffe", "tempete", "griffe", "griffe", "tempete"]);
+
+        assert!(choisir_attaque_ennemi(&mut rng, &Comportement::Aleatoire, &[], 100, 0).is_none());
+    }
+
+    #[test]
+    fn test_comportement_lu_depuis_le_json() {
+        let pnj: crate::modele::PnjType = serde_json::from_str(
+            r#"{ "type": "Ennemi", "puissance": 20, "hp": 150, "attaques": ["a", "b"], "required_items": [],
+                 "comportement": { "type": "Pondere", "poids": [3, 1] } }"#,
+        ).unwrap();
+        match pnj {
+            crate::modele::PnjType::Ennemi { comportement, .. } => {
+                assert_eq!(comportement, Comportement::Pondere { poids: vec![3, 1] });
+            }
+            _ => panic!("Le PNJ devrait être un ennemi"),
+        }
+    }
+}
@@ -0,0 +1,164 @@
+// Entrées/sorties du jeu : toutes les questions posées au joueur et tous les messages passent par une Console
+
+use std::collections::VecDeque;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+pub trait Console {
+    // Afficher une ligne de texte
+    fn afficher(&mut self, texte: &str);
+
+    // Afficher un texte sans retour à la ligne (invite de saisie)
+    fn ecrire(&mut self, texte: &str);
+
+    // Lire une ligne saisie, sans le retour à la ligne ; None quand l'entrée est terminée
+    fn lire_ligne(&mut self) -> Option<String>;
+
+    // Marquer une pause dans l'affichage (effets dramatiques, fin de partie)
+    fn pause(&mut self, duree: Duration);
+}
+
+// Afficher une ligne formatée sur une console, comme println!
+#[macro_export]
+macro_rules! afficher {
+    ($console:expr) => {
+        $console.afficher("")
+    };
+    ($console:expr, $($arg:tt)*) => {
+        $console.afficher(&format!($($arg)*))
+    };
+}
+
+// Console du terminal : entrée standard et sortie standard
+#[derive(Debug, Default)]
+pub struct ConsoleStandard;
+
+impl Console for ConsoleStandard {
+    fn afficher(&mut self, texte: &str) {
+        println!("{}", texte);
+    }
+
+    fn ecrire(&mut self, texte: &str) {
+        print!("{}", texte);
+        let _ = io::stdout().flush();
+    }
+
+    fn lire_ligne(&mut self) -> Option<String> {
+        let mut ligne = String::new();
+        match io::stdin().read_line(&mut ligne) {
+            Ok(0) | Err(_) => None,
+            Ok(_) => Some(ligne.trim_end_matches(['\n', '\r']).to_string()),
+        }
+    }
+
+    fn pause(&mut self, duree: Duration) {
+        thread::sleep(duree);
+    }
+}
+
+// Console en mémoire : les réponses sont fournies à l'avance et tout l'affichage est conservé.
+// Les pauses sont ignorées, ce qui permet de tester les parcours interactifs.
+#[derive(Debug, Default)]
+pub struct ConsoleMemoire {
+    entrees: VecDeque<String>,
+    sortie: String,
+}
+
+impl ConsoleMemoire {
+    pub fn new<S: Into<String>>(entrees: impl IntoIterator<Item = S>) -> ConsoleMemoire {
+        ConsoleMemoire {
+            entrees: entrees.into_iter().map(Into::into).collect(),
+            sortie: String::new(),
+        }
+    }
+
+    // Tout ce qui a été affiché jusqu'ici
+    pub fn sortie(&self) -> &str {
+        &self.sortie
+    }
+
+    // Nombre de réponses pas encore lues
+    pub fn entrees_restantes(&self) -> usize {
+        self.entrees.len()
+    }
+}
+
+impl Console for ConsoleMemoire {
+    fn afficher(&mut self, texte: &str) {
+        self.sortie.push_str(texte);
+        self.sortie.push('\n');
+    }
+
+    fn ecrire(&mut self, texte: &str) {
+        self.sortie.push_str(texte);
+    }
+
+    fn lire_ligne(&mut self) -> Option<String> {
+        self.entrees.pop_front()
+    }
+
+    fn pause(&mut self, _duree: Duration) {}
+}
+
+// Console de rejeu : les réponses sont lues dans un fichier de commandes, une par ligne,
+// et la transcription complète (questions et réponses) est écrite sur la sortie standard.
+// Les lignes commençant par '#' sont des commentaires ; les pauses sont ignorées.
+#[derive(Debug)]
+pub struct ConsoleScript {
+    entrees: VecDeque<String>,
+}
+
+impl ConsoleScript {
+    pub fn new(contenu: &str) -> ConsoleScript {
+        ConsoleScript {
+            entrees: contenu
+                .lines()
+                .filter(|ligne| !ligne.starts_with('#'))
+                .map(str::to_string)
+                .collect(),
+        }
+    }
+
+    pub fn depuis_fichier(chemin: &Path) -> Result<ConsoleScript, String> {
+        let contenu = fs::read_to_string(chemin)
+            .map_err(|e| format!("Impossible de lire le script {} : {}", chemin.display(), e))?;
+        Ok(ConsoleScript::new(&contenu))
+    }
+}
+
+impl Console for ConsoleScript {
+    fn afficher(&mut self, texte: &str) {
+        println!("{}", texte);
+    }
+
+    fn ecrire(&mut self, texte: &str) {
+        print!("{}", texte);
+    }
+
+    fn lire_ligne(&mut self) -> Option<String> {
+        let ligne = self.entrees.pop_front()?;
+        // Répéter la réponse pour que la transcription se lise comme une session au terminal
+        println!("{}", ligne);
+        Some(ligne)
+    }
+
+    fn pause(&mut self, _duree: Duration) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_console_script_ignore_les_commentaires() {
+        let mut console = ConsoleScript::new("# Nouvelle partie\nN\npartie1\r\n\n# Fin\nQ\n");
+        assert_eq!(console.lire_ligne().as_deref(), Some("N"));
+        assert_eq!(console.lire_ligne().as_deref(), Some("partie1"));
+        assert_eq!(console.lire_ligne().as_deref(), Some(""));
+        assert_eq!(console.lire_ligne().as_deref(), Some("Q"));
+        assert_eq!(console.lire_ligne(), None);
+    }
+}
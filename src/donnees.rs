@@ -0,0 +1,367 @@
+// Chargement des fichiers de données d'un monde et vérification de leur cohérence
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use crate::combat::ENERGIE_MAX;
+use crate::modele::*;
+use crate::monde::Monde;
+
+// Manifeste d'un monde : la liste des fichiers de données qui le composent
+pub const FICHIER_MANIFESTE: &str = "world.json";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Manifeste {
+    #[serde(default)]
+    pub nom: String,
+    pub fichiers: Vec<String>,
+}
+
+// Fichiers de données utilisés quand le dossier ne contient pas de manifeste
+pub const FICHIERS_MONDE: [&str; 8] = [
+    "joueur.json",
+    "lieu.json",
+    "sous_lieux.json",
+    "objetstatic.json",
+    "pnj.json",
+    "fruitdemon.json",
+    "aliments.json",
+    "objetmobile.json",
+];
+
+// Lire le manifeste du dossier de données (liste par défaut s'il n'y en a pas)
+fn lire_manifeste(dossier: &Path) -> Result<Manifeste, String> {
+    let chemin = dossier.join(FICHIER_MANIFESTE);
+    if !chemin.exists() {
+        return Ok(Manifeste {
+            nom: String::new(),
+            fichiers: FICHIERS_MONDE.iter().map(|f| f.to_string()).collect(),
+        });
+    }
+    let contenu = fs::read_to_string(&chemin)
+        .map_err(|e| format!("Impossible de lire le manifeste {} : {}", chemin.display(), e))?;
+    serde_json::from_str(&contenu)
+        .map_err(|e| format!("Manifeste invalide {} : {}", chemin.display(), e))
+}
+
+// Charger et combiner les fichiers de données du monde ; renvoie les objets lus et les erreurs rencontrées
+pub fn charger_fichiers_monde(dossier: &Path) -> (Vec<Objet>, Vec<String>) {
+    let mut objets = Vec::new();
+    let mut erreurs = Vec::new();
+
+    let manifeste = match lire_manifeste(dossier) {
+        Ok(manifeste) => manifeste,
+        Err(e) => return (objets, vec![e]),
+    };
+
+    for filename in &manifeste.fichiers {
+        let chemin = dossier.join(filename);
+        match fs::read_to_string(&chemin) {
+            Ok(content) => parser_fichier_monde(&chemin.display().to_string(), &content, &mut objets, &mut erreurs),
+            Err(e) => erreurs.push(format!("Impossible de lire le fichier {} : {}", chemin.display(), e)),
+        }
+    }
+
+    (objets, erreurs)
+}
+
+// Ajouter les objets d'un fichier de données, ou l'erreur de parsing rencontrée
+fn parser_fichier_monde(nom: &str, contenu: &str, objets: &mut Vec<Objet>, erreurs: &mut Vec<String>) {
+    match serde_json::from_str::<Vec<Objet>>(contenu) {
+        Ok(parsed_objects) => objets.extend(parsed_objects),
+        Err(e) => erreurs.push(format!("Erreur de parsing JSON dans {} : {}", nom, e)),
+    }
+}
+
+// Monde One Piece par défaut, compilé dans l'exécutable
+pub const MONDE_INTEGRE: [(&str, &str); 8] = [
+    ("joueur.json", include_str!("joueur.json")),
+    ("lieu.json", include_str!("lieu.json")),
+    ("sous_lieux.json", include_str!("sous_lieux.json")),
+    ("objetstatic.json", include_str!("objetstatic.json")),
+    ("pnj.json", include_str!("pnj.json")),
+    ("fruitdemon.json", include_str!("fruitdemon.json")),
+    ("aliments.json", include_str!("aliments.json")),
+    ("objetmobile.json", include_str!("objetmobile.json")),
+];
+
+// Charger le monde depuis un dossier de données, ou le monde intégré si aucun dossier n'est donné
+pub fn charger_monde(dossier: Option<&Path>) -> (Vec<Objet>, Vec<String>) {
+    if let Some(dossier) = dossier {
+        return charger_fichiers_monde(dossier);
+    }
+    let mut objets = Vec::new();
+    let mut erreurs = Vec::new();
+    for (nom, contenu) in MONDE_INTEGRE {
+        parser_fichier_monde(nom, contenu, &mut objets, &mut erreurs);
+    }
+    (objets, erreurs)
+}
+
+// Un objet ramassable (objet statique ou aliment) existe-t-il avec cet identifiant ?
+// Sert aux objets que le joueur doit posséder ; l'inventaire d'un PNJ, lui, ne contient que des objets statiques
+fn objet_ramassable_existe(monde: &Monde, id: &str) -> bool {
+    monde.objet_statique(id).is_some() || monde.index_par_id(TypeObjet::Aliment, id).is_some()
+}
+
+// Vérifier l'intégrité référentielle du monde ; renvoie la liste des problèmes trouvés
+pub fn valider_monde(objets: &[Objet]) -> Vec<String> {
+    let monde = Monde::new(objets.to_vec());
+    let mut problemes = Vec::new();
+
+    // Identifiants en double
+    let mut vus = HashSet::new();
+    for obj in objets {
+        if let Some(id) = obj.id() {
+            if !vus.insert((obj.type_objet(), id)) {
+                problemes.push(format!("Identifiant en double : {:?} '{}'", obj.type_objet(), id));
+            }
+        }
+    }
+
+    for obj in objets {
+        match obj {
+            Objet::Lieu(lieu) => {
+                for conn in &lieu.connections {
+                    if monde.lieu(&conn.destination).is_none() {
+                        problemes.push(format!("Lieu '{}' : connexion {} vers un lieu inexistant '{}'", lieu.id, conn.orientation, conn.destination));
+                    }
+                }
+                if !lieu.required_key.is_empty() && !objet_ramassable_existe(&monde, &lieu.required_key) {
+                    problemes.push(format!("Lieu '{}' : clé requise inexistante '{}'", lieu.id, lieu.required_key));
+                }
+                if !monde.sous_lieux(&lieu.id).any(|sl| sl.id.starts_with("SE")) {
+                    problemes.push(format!("Lieu '{}' : aucun sous-lieu \"SE\" où accoster en bateau", lieu.id));
+                }
+            }
+            Objet::SousLieu(sl) => {
+                if monde.lieu(&sl.position).is_none() {
+                    problemes.push(format!("Sous-lieu '{}' : lieu inexistant '{}'", sl.id, sl.position));
+                }
+                for conn in &sl.connections {
+                    if monde.sous_lieu(&sl.position, &conn.destination).is_none() {
+                        problemes.push(format!("Sous-lieu '{}' : connexion {} vers un sous-lieu inexistant '{}'", sl.id, conn.orientation, conn.destination));
+                    }
+                }
+            }
+            Objet::Pnj(pnj) => {
+                for id in &pnj.inventaire {
+                    if monde.objet_statique(id).is_none() {
+                        problemes.push(format!("PNJ '{}' : objet d'inventaire inexistant '{}'", pnj.nom, id));
+                    }
+                }
+            }
+            Objet::PnjAvecType(p) => {
+                for id in &p.pnj.inventaire {
+                    if monde.objet_statique(id).is_none() {
+                        problemes.push(format!("PNJ '{}' : objet d'inventaire inexistant '{}'", p.pnj.nom, id));
+                    }
+                }
+                if let PnjType::Ennemi { attaques, required_items, comportement, hp, hp_max, .. } = &p.type_de_pnj {
+                    if hp_max.is_some_and(|max| *hp > max) {
+                        problemes.push(format!("PNJ '{}' : HP ({}) supérieurs à hp_max", p.pnj.nom, hp));
+                    }
+                    for id in attaques {
+                        if monde.attaque(id).is_none() {
+                            problemes.push(format!("PNJ '{}' : attaque inexistante '{}'", p.pnj.nom, id));
+                        }
+                    }
+                    for id in required_items {
+                        if !objet_ramassable_existe(&monde, id) {
+                            problemes.push(format!("PNJ '{}' : objet requis inexistant '{}'", p.pnj.nom, id));
+                        }
+                    }
+                    match comportement {
+                        Comportement::Pondere { poids } if poids.len() != attaques.len() => {
+                            problemes.push(format!("PNJ '{}' : {} poids pour {} attaques", p.pnj.nom, poids.len(), attaques.len()));
+                        }
+                        Comportement::Finisseur { motif } => {
+                            for id in motif.iter().filter(|id| !attaques.contains(id)) {
+                                problemes.push(format!("PNJ '{}' : attaque du motif absente de ses attaques '{}'", p.pnj.nom, id));
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            Objet::Aliment(aliment) if aliment.quantite == 0 => {
+                problemes.push(format!("Aliment '{}' : quantité nulle", aliment.id));
+            }
+            Objet::Joueur(joueur) if joueur.hp > joueur.hp_max => {
+                problemes.push(format!("Joueur : HP ({}) supérieurs à hp_max ({})", joueur.hp, joueur.hp_max));
+            }
+            Objet::Attaque(attaque) if attaque.cout > ENERGIE_MAX => {
+                problemes.push(format!("Attaque '{}' : coût {} supérieur à l'énergie maximale ({})", attaque.id, attaque.cout, ENERGIE_MAX));
+            }
+            Objet::FruitDuDemon(fruit) => {
+                for id in &fruit.attaque {
+                    if monde.attaque(id).is_none() {
+                        problemes.push(format!("Fruit '{}' : attaque inexistante '{}'", fruit.id, id));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Sous-lieux inaccessibles depuis le point d'accostage "SE" de leur lieu
+    for lieu in monde.de_type(TypeObjet::Lieu) {
+        let Objet::Lieu(lieu) = lieu else { continue };
+        let mut a_visiter: Vec<&str> = monde.sous_lieux(&lieu.id)
+            .filter(|sl| sl.id.starts_with("SE"))
+            .map(|sl| sl.id.as_str())
+            .collect();
+        if a_visiter.is_empty() {
+            continue; // Déjà signalé plus haut
+        }
+        let mut accessibles = HashSet::new();
+        while let Some(id) = a_visiter.pop() {
+            if !accessibles.insert(id) {
+                continue;
+            }
+            if let Some(sl) = monde.sous_lieu(&lieu.id, id) {
+                a_visiter.extend(sl.connections.iter().map(|c| c.destination.as_str()));
+            }
+        }
+        for sl in monde.sous_lieux(&lieu.id) {
+            if !accessibles.contains(sl.id.as_str()) {
+                problemes.push(format!("Sous-lieu '{}' : inaccessible depuis le point d'accostage de '{}'", sl.id, lieu.id));
+            }
+        }
+    }
+
+    problemes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests_communs::*;
+    use crate::monde::EtatJeu;
+
+    #[test]
+    fn test_valider_monde_coherent() {
+        let mut pnj = creer_pnj_ennemi();
+        pnj.pnj.inventaire = vec![];
+        let mut se = creer_sous_lieu_test("SEALABASTA", "piece1");
+        se.connections = vec![Connection { orientation: "N".to_string(), destination: "NEALABASTA".to_string() }];
+        let objets = vec![
+            Objet::Lieu(creer_lieu_test("piece1", "", vec![])),
+            Objet::SousLieu(se),
+            Objet::SousLieu(creer_sous_lieu_test("NEALABASTA", "piece1")),
+            Objet::PnjAvecType(pnj),
+            Objet::Attaque(creer_attaque_test()),
+        ];
+        assert!(valider_monde(&objets).is_empty(), "{:?}", valider_monde(&objets));
+    }
+
+    #[test]
+    fn test_valider_monde_signale_les_references_cassees() {
+        let connexion = Connection { orientation: "N".to_string(), destination: "piece9".to_string() };
+        let mut pnj = creer_pnj_ennemi();
+        // Un PNJ ne peut donner que des objets statiques, mais un aliment peut être exigé pour le combattre
+        pnj.pnj.inventaire = vec!["tresor".to_string(), "riz".to_string()];
+        if let PnjType::Ennemi { ref mut required_items, .. } = pnj.type_de_pnj {
+            *required_items = vec!["riz".to_string()];
+        }
+        let riz = Aliment {
+            id: "riz".to_string(),
+            nom: "Riz".to_string(),
+            description: "Un bol de riz".to_string(),
+            position: "piece1".to_string(),
+            sous_position: "NOALABASTA".to_string(),
+            hp: 20,
+            bonus_hp_max: 0,
+            quantite: 1,
+            effet: None,
+        };
+        let objets = vec![
+            Objet::Aliment(riz),
+            Objet::Lieu(creer_lieu_test("piece1", "cle", vec![connexion])),
+            Objet::Lieu(creer_lieu_test("piece1", "", vec![])),
+            Objet::SousLieu(creer_sous_lieu_test("NOALABASTA", "piece1")),
+            Objet::PnjAvecType(pnj),
+        ];
+        let problemes = valider_monde(&objets);
+
+        let contient = |motif: &str| problemes.iter().any(|p| p.contains(motif));
+        assert!(contient("Identifiant en double : Lieu 'piece1'"));
+        assert!(contient("lieu inexistant 'piece9'"));
+        assert!(contient("clé requise inexistante 'cle'"));
+        assert!(contient("aucun sous-lieu \"SE\""));
+        assert!(contient("attaque inexistante 'attaque1'"));
+        assert!(contient("objet d'inventaire inexistant 'tresor'"));
+        assert!(contient("objet d'inventaire inexistant 'riz'"));
+        assert!(!contient("objet requis inexistant"));
+    }
+
+    #[test]
+    fn test_valider_monde_sous_lieu_inaccessible() {
+        let objets = vec![
+            Objet::Lieu(creer_lieu_test("piece1", "", vec![])),
+            Objet::SousLieu(creer_sous_lieu_test("SEALABASTA", "piece1")),
+            Objet::SousLieu(creer_sous_lieu_test("NOALABASTA", "piece1")),
+        ];
+        assert_eq!(
+            valider_monde(&objets),
+            vec!["Sous-lieu 'NOALABASTA' : inaccessible depuis le point d'accostage de 'piece1'".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_valider_monde_comportements_ennemis() {
+        let mut pondere = creer_pnj_ennemi();
+        if let PnjType::Ennemi { ref mut comportement, .. } = pondere.type_de_pnj {
+            *comportement = Comportement::Pondere { poids: vec![1, 2] };
+        }
+        let mut finisseur = creer_pnj_ennemi();
+        finisseur.pnj.nom = "Finisseur".to_string();
+        if let PnjType::Ennemi { ref mut comportement, .. } = finisseur.type_de_pnj {
+            *comportement = Comportement::Finisseur { motif: vec!["attaque1".to_string(), "coupfinal".to_string()] };
+        }
+        let objets = vec![Objet::PnjAvecType(pondere), Objet::PnjAvecType(finisseur), Objet::Attaque(creer_attaque_test())];
+        assert_eq!(
+            valider_monde(&objets),
+            vec![
+                "PNJ 'PNJ Ennemi' : 2 poids pour 1 attaques".to_string(),
+                "PNJ 'Finisseur' : attaque du motif absente de ses attaques 'coupfinal'".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_charger_monde_depuis_manifeste() {
+        let dossier = std::env::temp_dir().join("onepiece_test_manifeste");
+        let _ = fs::remove_dir_all(&dossier);
+        fs::create_dir_all(dossier.join("iles")).unwrap();
+
+        let manifeste = Manifeste { nom: "Test".to_string(), fichiers: vec!["iles/lieux.json".to_string()] };
+        fs::write(dossier.join(FICHIER_MANIFESTE), serde_json::to_string(&manifeste).unwrap()).unwrap();
+        let lieux = vec![Objet::Lieu(creer_lieu_test("piece1", "", vec![]))];
+        fs::write(dossier.join("iles/lieux.json"), serde_json::to_string(&lieux).unwrap()).unwrap();
+
+        let (objets, erreurs) = charger_fichiers_monde(&dossier);
+        assert!(erreurs.is_empty(), "{:?}", erreurs);
+        assert_eq!(objets.len(), 1);
+
+        fs::remove_dir_all(&dossier).unwrap();
+    }
+
+    #[test]
+    fn test_monde_par_defaut_valide() {
+        let (objets, erreurs) = charger_fichiers_monde(Path::new("src"));
+        assert!(erreurs.is_empty(), "{:?}", erreurs);
+        assert!(valider_monde(&objets).is_empty(), "{:?}", valider_monde(&objets));
+    }
+
+    #[test]
+    fn test_monde_integre_identique_au_dossier_src() {
+        let (integre, erreurs) = charger_monde(None);
+        assert!(erreurs.is_empty(), "{:?}", erreurs);
+        let (dossier, _) = charger_monde(Some(Path::new("src")));
+        assert_eq!(integre.len(), dossier.len());
+        assert!(EtatJeu::depuis_objets(integre).is_ok());
+    }
+}
@@ -0,0 +1,134 @@
+// Effets de statut (brûlure, poison, étourdissement, bonus) portés par le joueur et les ennemis
+
+use std::fmt;
+use std::mem;
+
+use crate::afficher;
+use crate::console::Console;
+use crate::modele::{Duree, Effet, EffetStatut};
+
+impl fmt::Display for Effet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Effet::Brulure { degats } => write!(f, "brûlure ({} dégâts par tour)", degats),
+            Effet::Poison { degats } => write!(f, "poison ({} dégâts par tour)", degats),
+            Effet::Etourdissement => write!(f, "étourdissement"),
+            Effet::BonusPuissance { bonus } => write!(f, "bonus de puissance (+{})", bonus),
+        }
+    }
+}
+
+impl fmt::Display for EffetStatut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.duree {
+            Duree::Actions(n) => write!(f, "{} pendant {} action(s)", self.effet, n),
+            Duree::ToursDeCombat(n) => write!(f, "{} pendant {} tour(s) de combat", self.effet, n),
+        }
+    }
+}
+
+// Ajouter un effet ; un effet du même genre déjà actif est remplacé plutôt que cumulé
+pub fn ajouter_effet(effets: &mut Vec<EffetStatut>, nouveau: EffetStatut) {
+    effets.retain(|e| mem::discriminant(&e.effet) != mem::discriminant(&nouveau.effet));
+    effets.push(nouveau);
+}
+
+pub fn bonus_puissance(effets: &[EffetStatut]) -> u32 {
+    effets
+        .iter()
+        .map(|e| match e.effet {
+            Effet::BonusPuissance { bonus } => bonus,
+            _ => 0,
+        })
+        .sum()
+}
+
+pub fn est_etourdi(effets: &[EffetStatut]) -> bool {
+    effets.iter().any(|e| e.effet == Effet::Etourdissement)
+}
+
+// Faire passer une action du menu de jeu (en_combat = false) ou un tour de combat (en_combat = true) :
+// les effets de cette durée infligent leurs dégâts, puis s'usent et disparaissent une fois écoulés
+pub fn ecouler_effets(effets: &mut Vec<EffetStatut>, hp: &mut u32, nom: &str, en_combat: bool, console: &mut dyn Console) {
+    effets.retain_mut(|statut| {
+        let restant = match &mut statut.duree {
+            Duree::Actions(n) if !en_combat => n,
+            Duree::ToursDeCombat(n) if en_combat => n,
+            _ => return true,
+        };
+        match statut.effet {
+            Effet::Brulure { degats } => {
+                *hp = hp.saturating_sub(degats);
+                afficher!(console, "🔥 {} subit {} dégâts de brûlure! HP: {}", nom, degats, hp);
+            }
+            Effet::Poison { degats } => {
+                *hp = hp.saturating_sub(degats).max(1).min(*hp);
+                afficher!(console, "☠️ {} souffre du poison! HP: {}", nom, hp);
+            }
+            Effet::Etourdissement | Effet::BonusPuissance { .. } => {}
+        }
+        *restant = restant.saturating_sub(1);
+        if *restant == 0 {
+            afficher!(console, "L'effet {} de {} se dissipe.", statut.effet, nom);
+        }
+        *restant > 0
+    });
+}
+
+// Retirer les effets qui ne durent que le temps d'un combat
+pub fn terminer_combat(effets: &mut Vec<EffetStatut>) {
+    effets.retain(|e| !matches!(e.duree, Duree::ToursDeCombat(_)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::console::ConsoleMemoire;
+
+    fn effet(effet: Effet, duree: Duree) -> EffetStatut {
+        EffetStatut { effet, duree }
+    }
+
+    #[test]
+    fn test_effets_s_ecoulent_et_expirent() {
+        let mut console = ConsoleMemoire::default();
+        let mut effets = Vec::new();
+        ajouter_effet(&mut effets, effet(Effet::Brulure { degats: 10 }, Duree::ToursDeCombat(2)));
+        ajouter_effet(&mut effets, effet(Effet::BonusPuissance { bonus: 15 }, Duree::Actions(3)));
+        let mut hp = 25;
+
+        // Une action hors combat n'use que les effets comptés en actions
+        ecouler_effets(&mut effets, &mut hp, "Luffy", false, &mut console);
+        assert_eq!(hp, 25);
+        assert_eq!(bonus_puissance(&effets), 15);
+
+        ecouler_effets(&mut effets, &mut hp, "Luffy", true, &mut console);
+        ecouler_effets(&mut effets, &mut hp, "Luffy", true, &mut console);
+        assert_eq!(hp, 5);
+        assert_eq!(effets, vec![effet(Effet::BonusPuissance { bonus: 15 }, Duree::Actions(2))]);
+
+        ecouler_effets(&mut effets, &mut hp, "Luffy", false, &mut console);
+        ecouler_effets(&mut effets, &mut hp, "Luffy", false, &mut console);
+        assert!(effets.is_empty());
+        assert_eq!(bonus_puissance(&effets), 0);
+        assert!(console.sortie().contains("L'effet bonus de puissance (+15) de Luffy se dissipe."));
+    }
+
+    #[test]
+    fn test_poison_laisse_un_hp_et_effets_remplaces() {
+        let mut console = ConsoleMemoire::default();
+        let mut effets = vec![effet(Effet::Poison { degats: 5 }, Duree::ToursDeCombat(3))];
+        ajouter_effet(&mut effets, effet(Effet::Poison { degats: 50 }, Duree::ToursDeCombat(3)));
+        ajouter_effet(&mut effets, effet(Effet::Etourdissement, Duree::ToursDeCombat(1)));
+        assert_eq!(effets.len(), 2);
+        assert!(est_etourdi(&effets));
+
+        let mut hp = 30;
+        ecouler_effets(&mut effets, &mut hp, "Kaido", true, &mut console);
+        assert_eq!(hp, 1);
+        assert!(!est_etourdi(&effets));
+
+        terminer_combat(&mut effets);
+        assert!(effets.is_empty());
+    }
+}
@@ -0,0 +1,128 @@
+// Équipement du joueur : arme, armure et accessoire qui modifient ses caractéristiques
+
+use std::fmt;
+
+use crate::afficher;
+use crate::console::Console;
+use crate::modele::{Emplacement, Equipable, Joueur, ObjetInventaire};
+
+impl fmt::Display for Emplacement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let nom = match self {
+            Emplacement::Arme => "Arme",
+            Emplacement::Armure => "Armure",
+            Emplacement::Accessoire => "Accessoire",
+        };
+        write!(f, "{}", nom)
+    }
+}
+
+impl Emplacement {
+    pub fn depuis_nom(nom: &str) -> Option<Emplacement> {
+        match nom.trim().to_lowercase().as_str() {
+            "arme" | "a" => Some(Emplacement::Arme),
+            "armure" | "r" => Some(Emplacement::Armure),
+            "accessoire" | "c" => Some(Emplacement::Accessoire),
+            _ => None,
+        }
+    }
+}
+
+// Bonus d'un objet équipable, ex. " (+15 puissance, +3 défense)"
+pub fn description_bonus(equipable: &Equipable) -> String {
+    let mut bonus = Vec::new();
+    if equipable.puissance > 0 {
+        bonus.push(format!("+{} puissance", equipable.puissance));
+    }
+    if equipable.defense > 0 {
+        bonus.push(format!("+{} défense", equipable.defense));
+    }
+    if bonus.is_empty() {
+        String::new()
+    } else {
+        format!(" ({})", bonus.join(", "))
+    }
+}
+
+// Équiper l'objet d'inventaire à cet index ; l'objet déjà porté au même emplacement retourne dans l'inventaire
+pub fn equiper(joueur: &mut Joueur, index: usize, console: &mut dyn Console) -> bool {
+    let emplacement = match joueur.inventaire.get(index) {
+        Some(ObjetInventaire::ObjetStatique(o)) => match &o.equipable {
+            Some(equipable) => equipable.emplacement,
+            None => {
+                afficher!(console, "{} ne peut pas être équipé.", o.nom);
+                return false;
+            }
+        },
+        Some(ObjetInventaire::Aliment(a)) => {
+            afficher!(console, "{} ne peut pas être équipé.", a.nom);
+            return false;
+        }
+        None => {
+            afficher!(console, "Aucun objet à cet emplacement de l'inventaire.");
+            return false;
+        }
+    };
+
+    retirer(joueur, emplacement, console);
+    let ObjetInventaire::ObjetStatique(objet) = joueur.inventaire.remove(index) else {
+        unreachable!("l'objet a été vérifié plus haut");
+    };
+    afficher!(console, "🗡️ Vous équipez {} ({}).", objet.nom, emplacement);
+    joueur.equipement.push(objet);
+    true
+}
+
+// Retirer l'objet porté à cet emplacement et le remettre dans l'inventaire
+pub fn retirer(joueur: &mut Joueur, emplacement: Emplacement, console: &mut dyn Console) -> bool {
+    let Some(position) = joueur.equipement.iter()
+        .position(|o| o.equipable.as_ref().is_some_and(|e| e.emplacement == emplacement))
+    else {
+        return false;
+    };
+    let objet = joueur.equipement.remove(position);
+    afficher!(console, "Vous retirez {}.", objet.nom);
+    joueur.inventaire.push(ObjetInventaire::ObjetStatique(objet));
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::console::ConsoleMemoire;
+    use crate::modele::ObjetStatique;
+    use crate::tests_communs::*;
+
+    fn arme(id: &str, puissance: u32) -> ObjetInventaire {
+        ObjetInventaire::ObjetStatique(ObjetStatique {
+            id: id.to_string(),
+            nom: id.to_string(),
+            description: String::new(),
+            position: "inventaire".to_string(),
+            sous_position: "inventaire".to_string(),
+            equipable: Some(Equipable { emplacement: Emplacement::Arme, puissance, defense: 0 }),
+        })
+    }
+
+    #[test]
+    fn test_equiper_remplace_l_arme_portee() {
+        let mut joueur = creer_joueur_test();
+        joueur.inventaire = vec![arme("epee", 15), arme("sabre", 5)];
+        let mut console = ConsoleMemoire::default();
+
+        assert!(equiper(&mut joueur, 0, &mut console));
+        assert_eq!(joueur.puissance_effective(), 25);
+        assert!(joueur.possede("epee"));
+
+        // Le sabre prend la place de l'épée, qui retourne dans l'inventaire
+        assert!(equiper(&mut joueur, 0, &mut console));
+        assert_eq!(joueur.puissance_effective(), 15);
+        assert_eq!(joueur.equipement.len(), 1);
+        assert!(joueur.possede("epee"));
+
+        assert!(retirer(&mut joueur, Emplacement::Arme, &mut console));
+        assert!(!retirer(&mut joueur, Emplacement::Arme, &mut console));
+        assert_eq!(joueur.puissance_effective(), 10);
+        assert_eq!(joueur.inventaire.len(), 2);
+    }
+}
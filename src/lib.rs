@@ -0,0 +1,17 @@
+// Moteur du jeu One Piece : modèle du monde, règles du jeu, chargement des données et sauvegardes.
+// Le binaire OnePiece n'est qu'une interface en ligne de commande au-dessus de cette bibliothèque.
+
+pub mod combat;
+pub mod console;
+pub mod donnees;
+pub mod effets;
+pub mod equipement;
+pub mod mini_jeux;
+pub mod modele;
+pub mod monde;
+pub mod moteur;
+pub mod progression;
+pub mod sauvegarde;
+
+#[cfg(test)]
+mod tests_communs;
@@ -0,0 +1,104 @@
+// Mini-jeux proposés depuis le menu principal ; chacun renvoie true si le joueur a gagné
+
+use rand::rngs::StdRng;
+use rand::Rng;
+
+use crate::afficher;
+use crate::console::Console;
+
+// Trois essais ne suffisent pas toujours, même en coupant l'intervalle en deux : la victoire n'est pas garantie
+const ESSAIS_DEVINETTE: u32 = 3;
+
+pub fn mini_jeu_devinette(console: &mut dyn Console, rng: &mut StdRng) -> bool {
+    let secret = rng.random_range(1..=10);
+    afficher!(console, "Je pense à un nombre entre 1 et 10. Devine en {} essais !", ESSAIS_DEVINETTE);
+    let mut essais = 0;
+    while essais < ESSAIS_DEVINETTE {
+        let Some(guess) = console.lire_ligne() else { return false };
+        let guess: u32 = match guess.trim().parse() {
+            Ok(num) => num,
+            Err(_) => {
+                afficher!(console, "Entre un nombre !");
+                continue;
+            }
+        };
+        essais += 1;
+        if guess == secret {
+            afficher!(console, "Bravo ! Trouvé en {} essais.", essais);
+            return true;
+        } else if guess < secret {
+            afficher!(console, "C'est plus grand !");
+        } else {
+            afficher!(console, "C'est plus petit !");
+        }
+    }
+    afficher!(console, "Perdu ! Le nombre était {}.", secret);
+    false
+}
+
+pub fn mini_jeu_pile_ou_face(console: &mut dyn Console, rng: &mut StdRng) -> bool {
+    afficher!(console, "Pile ou face ? (pile/face)");
+    let choix = console.lire_ligne().unwrap_or_default();
+    let choix = choix.trim().to_lowercase();
+    let tirage = if rng.random_bool(0.5) { "pile" } else { "face" };    afficher!(console, "Résultat : {}", tirage);
+    if choix == tirage {
+        afficher!(console, "Gagné !");
+        true
+    } else {
+        afficher!(console, "Perdu !");
+        false
+    }
+}
+
+pub fn mini_jeu_calcul(console: &mut dyn Console, rng: &mut StdRng) -> bool {
+    let a = rng.random_range(1..=10);
+    let b = rng.random_range(1..=10);
+    afficher!(console, "Combien font {} + {} ?", a, b);
+    let reponse = console.lire_ligne().unwrap_or_default();
+    let reponse: i32 = match reponse.trim().parse() {
+        Ok(num) => num,
+        Err(_) => {
+            afficher!(console, "Ce n'est pas un nombre !");
+            return false;
+        }
+    };
+    if reponse == a + b {
+        afficher!(console, "Bonne réponse !");
+        true
+    } else {
+        afficher!(console, "Faux ! La bonne réponse était {}.", a + b);
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::console::ConsoleMemoire;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_mini_jeux_reproductibles_avec_une_graine() {
+        let partie = || {
+            let mut rng = StdRng::seed_from_u64(7);
+            let mut console = ConsoleMemoire::new(["5", "3", "8", "pile"]);
+            mini_jeu_devinette(&mut console, &mut rng);
+            mini_jeu_pile_ou_face(&mut console, &mut rng);
+            console.sortie().to_string()
+        };
+        assert_eq!(partie(), partie());
+    }
+
+    #[test]
+    fn test_devinette_perdue_apres_trois_essais() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let secret: u32 = rng.clone().random_range(1..=10);
+        let faux = if secret == 1 { "2" } else { "1" };
+        // Une saisie invalide ne compte pas comme un essai, la quatrième réponse n'est jamais lue
+        let mut console = ConsoleMemoire::new(["abc", faux, faux, faux, &secret.to_string()]);
+
+        assert!(!mini_jeu_devinette(&mut console, &mut rng));
+        assert!(console.sortie().contains(&format!("Perdu ! Le nombre était {}.", secret)));
+        assert_eq!(console.lire_ligne(), Some(secret.to_string()));
+    }
+}
@@ -0,0 +1,490 @@
+// Modèle du monde : objets, personnages, lieux et joueur, tels que décrits dans les fichiers JSON
+
+use serde::{Deserialize, Serialize};
+
+use crate::combat::ENERGIE_MAX;
+use crate::effets::bonus_puissance;
+use crate::progression::HP_MAX_INITIAL;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Connection {
+    pub orientation: String,
+    pub destination: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ObjetStatique {
+    pub id: String,
+    pub nom: String,
+    pub description: String,
+    pub position: String,
+    pub sous_position:String,
+    // Emplacement et bonus si l'objet peut être équipé
+    #[serde(default)]
+    pub equipable: Option<Equipable>,
+}
+
+// Emplacements d'équipement du joueur, un objet au plus par emplacement
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Emplacement {
+    Arme,
+    Armure,
+    Accessoire,
+}
+
+// Bonus apportés par un objet tant qu'il est équipé
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Equipable {
+    pub emplacement: Emplacement,
+    #[serde(default)]
+    pub puissance: u32,
+    // Dégâts retranchés à chaque coup reçu
+    #[serde(default)]
+    pub defense: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Aliment {
+    pub id: String,
+    pub nom: String,
+    pub description: String,
+    pub position: String,
+    pub sous_position:String,
+    pub hp: u32, // Points de vie restaurés
+    // HP max gagnés définitivement en le mangeant
+    #[serde(default)]
+    pub bonus_hp_max: u32,
+    // Nombre d'exemplaires : les aliments identiques s'empilent dans l'inventaire
+    #[serde(default = "quantite_unitaire")]
+    pub quantite: u32,
+    // Effet de statut appliqué à celui qui le mange
+    #[serde(default)]
+    pub effet: Option<EffetStatut>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ObjetMobile {
+    pub id: String,
+    pub nom: String,
+    pub description: String,
+    pub position: String,
+    pub sous_position:String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Attaque {
+    pub id: String,
+    pub nom: String,
+    pub description: String,
+    pub puissance: u32,
+    // Énergie dépensée par le joueur pour lancer l'attaque
+    #[serde(default)]
+    pub cout: u32,
+    // Effet de statut infligé à la cible quand l'attaque touche
+    #[serde(default)]
+    pub effet: Option<EffetStatut>,
+    // Éléments de l'attaque, qui multiplient ses dégâts selon ceux de la cible
+    #[serde(default)]
+    pub elements: Vec<Element>,
+}
+
+// Types de fruits du démon et éléments, pour les faiblesses et résistances en combat
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Element {
+    Logia,
+    Paramecia,
+    Zoan,
+    Eau,
+    Feu,
+    Haki,
+}
+
+// Effets de statut : dégâts sur la durée, étourdissement ou bonus temporaire
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum Effet {
+    // Dégâts à chaque tour
+    Brulure { degats: u32 },
+    // Dégâts à chaque tour, sans jamais descendre sous 1 HP
+    Poison { degats: u32 },
+    // Le combattant perd son prochain tour
+    Etourdissement,
+    BonusPuissance { bonus: u32 },
+}
+
+// Durée restante d'un effet, en actions du menu de jeu ou en tours de combat
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Duree {
+    Actions(u32),
+    ToursDeCombat(u32),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EffetStatut {
+    pub effet: Effet,
+    pub duree: Duree,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FruitDuDemon {
+    pub id: String,
+    pub nom: String,
+    pub description: String,
+    pub sous_position:String,
+    pub pouvoir: String,
+    pub position: String, 
+    pub attaque: Vec<String>,
+    // Éléments du fruit, qui protègent (ou exposent) celui qui l'a mangé
+    #[serde(default)]
+    pub elements: Vec<Element>,
+}
+
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Joueur {
+    pub nom: String,
+    pub fruit_de_demon: Option<FruitDuDemon>,
+    pub position: String,
+    pub sous_position:String,
+    pub inventaire: Vec<ObjetInventaire>,
+    pub puissance: u32,
+    pub hp: u32,
+    // Sous-lieu d'où vient le joueur sur l'île actuelle, vers lequel il peut fuir un combat
+    #[serde(default)]
+    pub sous_position_precedente: Option<String>,
+    // Énergie (haki) disponible pour les attaques du fruit du démon
+    #[serde(default = "energie_initiale")]
+    pub energie: u32,
+    // Effets de statut en cours
+    #[serde(default)]
+    pub effets: Vec<EffetStatut>,
+    // Progression : expérience totale, niveau et HP maximum
+    #[serde(default)]
+    pub xp: u32,
+    #[serde(default = "niveau_initial")]
+    pub niveau: u32,
+    #[serde(default = "hp_max_initial")]
+    pub hp_max: u32,
+    // Objets équipés, retirés de l'inventaire le temps qu'ils sont portés
+    #[serde(default)]
+    pub equipement: Vec<ObjetStatique>,
+    // Mini-jeux déjà récompensés, un par île : "lieu/mini-jeu"
+    #[serde(default)]
+    pub mini_jeux_recompenses: Vec<String>,
+}
+
+fn quantite_unitaire() -> u32 {
+    1
+}
+
+fn energie_initiale() -> u32 {
+    ENERGIE_MAX
+}
+
+fn niveau_initial() -> u32 {
+    1
+}
+
+fn hp_max_initial() -> u32 {
+    HP_MAX_INITIAL
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "type_inventaire")]
+pub enum ObjetInventaire {
+    #[serde(rename = "objet")]
+    ObjetStatique(ObjetStatique),
+    
+    #[serde(rename = "aliment")]
+    Aliment(Aliment)
+}
+
+impl ObjetInventaire {
+    pub fn id(&self) -> &str {
+        match self {
+            ObjetInventaire::ObjetStatique(o) => &o.id,
+            ObjetInventaire::Aliment(a) => &a.id,
+        }
+    }
+
+    pub fn nom(&self) -> &str {
+        match self {
+            ObjetInventaire::ObjetStatique(o) => &o.nom,
+            ObjetInventaire::Aliment(a) => &a.nom,
+        }
+    }
+
+    // Les objets statiques sont uniques, seuls les aliments s'empilent
+    pub fn quantite(&self) -> u32 {
+        match self {
+            ObjetInventaire::ObjetStatique(_) => 1,
+            ObjetInventaire::Aliment(a) => a.quantite,
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Pnj {
+    pub nom: String,
+    pub description: String,
+    pub position: String,
+    pub sous_position:String,
+    pub inventaire: Vec<String>,
+}
+
+// Enum pour les différents types de PNJ avec leurs attributs spécifiques
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum PnjType {
+    #[serde(rename = "Ennemi")]
+    Ennemi {
+        puissance: u32,
+        hp: u32,
+        attaques: Vec<String>,
+        required_items: Vec<String>,
+        #[serde(default)]
+        comportement: Comportement,
+        #[serde(default)]
+        elements: Vec<Element>,
+        // HP de l'ennemi en pleine forme ; absents des fichiers de données, ce sont ses HP de départ
+        #[serde(default)]
+        hp_max: Option<u32>,
+        // HP récupérés chaque fois que le joueur quitte l'île de l'ennemi
+        #[serde(default)]
+        regeneration: u32,
+        // Un ennemi vaincu réapparaît en pleine forme quand le joueur quitte son île
+        #[serde(default)]
+        reapparait: bool,
+    },
+    #[serde(rename = "Gentil")]
+    Gentil {
+        dialogue_special: Option<String>,
+        // Objets donnés par le joueur, qu'il peut reprendre sans terminer de quête
+        #[serde(default)]
+        objets_recus: Vec<String>,
+    },
+    #[serde(rename = "Entraineur")]
+    Entraineur {
+        competence: String,
+        bonus_puissance: u32,
+        // HP max gagnés en plus de la puissance
+        #[serde(default)]
+        bonus_hp_max: u32,
+        // Niveau du joueur (voir progression) et puissance nécessaires pour s'entraîner
+        niveau_requis: u32,
+        #[serde(default)]
+        puissance_requise: u32,
+        // Chaque entraîneur n'entraîne le joueur qu'une seule fois
+        #[serde(default)]
+        deja_entraine: bool,
+    },
+}
+
+// Façon dont un ennemi choisit son attaque à chaque tour de combat
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum Comportement {
+    // Toujours la première attaque de la liste
+    #[default]
+    Premiere,
+    // Une attaque au hasard
+    Aleatoire,
+    // Au hasard, chaque attaque ayant le poids donné (même ordre que `attaques`)
+    Pondere { poids: Vec<u32> },
+    // Au hasard, puis toujours l'attaque la plus puissante une fois ses HP sous le seuil
+    Desespere { seuil_hp: u32 },
+    // Enchaîne les attaques du motif dans l'ordre, en boucle (ex : deux coups puis un coup final)
+    Finisseur { motif: Vec<String> },
+}
+
+// Structure combinée
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PnjAvecType {
+    pub pnj: Pnj,
+    pub type_de_pnj: PnjType,
+}
+
+// Traits pour les comportements spécifiques
+pub trait Combattant {
+    fn est_vaincu(&self) -> bool;
+}
+
+
+
+// Implémentations des traits
+
+
+impl Combattant for PnjAvecType {
+    
+    fn est_vaincu(&self) -> bool {
+        match &self.type_de_pnj {
+            PnjType::Ennemi { hp, .. } => *hp == 0,
+            _ => false
+        }
+    }
+}
+
+impl Joueur {
+    // Puissance en tenant compte des bonus temporaires et de l'équipement
+    pub fn puissance_effective(&self) -> u32 {
+        self.puissance + bonus_puissance(&self.effets) + self.equipables().map(|e| e.puissance).sum::<u32>()
+    }
+
+    // Défense apportée par l'équipement
+    pub fn defense(&self) -> u32 {
+        self.equipables().map(|e| e.defense).sum()
+    }
+
+    fn equipables(&self) -> impl Iterator<Item = &Equipable> {
+        self.equipement.iter().filter_map(|o| o.equipable.as_ref())
+    }
+
+    // Regagner de l'énergie, sans dépasser le maximum
+    pub fn recuperer_energie(&mut self, quantite: u32) {
+        self.energie = (self.energie + quantite).min(ENERGIE_MAX);
+    }
+
+    // Vérifier si le joueur possède un objet (ou aliment) dans son inventaire ou sur lui
+    pub fn possede(&self, id: &str) -> bool {
+        self.equipement.iter().any(|o| o.id == id) || self.inventaire.iter().any(|item| item.id() == id)
+    }
+
+    // Ajouter un objet à l'inventaire ; un aliment déjà présent voit sa quantité augmenter
+    pub fn ajouter_objet(&mut self, objet: ObjetInventaire) {
+        if let ObjetInventaire::Aliment(nouveau) = &objet {
+            let pile = self.inventaire.iter_mut().find_map(|item| match item {
+                ObjetInventaire::Aliment(a) if a.id == nouveau.id => Some(a),
+                _ => None,
+            });
+            if let Some(pile) = pile {
+                pile.quantite += nouveau.quantite;
+                return;
+            }
+        }
+        self.inventaire.push(objet);
+    }
+
+    // Retirer jusqu'à `quantite` exemplaires de l'objet à cet index ; renvoie la partie retirée
+    pub fn retirer_objet(&mut self, index: usize, quantite: u32) -> Option<ObjetInventaire> {
+        match self.inventaire.get_mut(index)? {
+            ObjetInventaire::Aliment(a) if quantite < a.quantite => {
+                a.quantite -= quantite;
+                Some(ObjetInventaire::Aliment(Aliment { quantite, ..a.clone() }))
+            }
+            _ => Some(self.inventaire.remove(index)),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Lieu {
+    pub id: String,
+    pub nom: String,
+    pub description: String,
+    pub connections: Vec<Connection>,
+    pub required_key: String, // Clé requise pour accéder à ce lieu
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SousLieu {
+    pub id: String,
+    pub nom: String,
+    pub position: String,
+    pub description: String,
+    pub connections: Vec<Connection>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "type")]
+pub enum Objet {
+    #[serde(rename = "ObjetMobile")]
+    ObjetMobile(ObjetMobile),
+    
+    #[serde(rename = "ObjetStatique")]
+    ObjetStatique(ObjetStatique),
+    
+    #[serde(rename = "Pnj")]
+    Pnj(Pnj),
+
+    #[serde(rename = "PnjAvecType")]
+    PnjAvecType(PnjAvecType),
+    
+    #[serde(rename = "Joueur")]
+    Joueur(Joueur),
+
+    #[serde(rename = "FruitDuDemon")]
+    FruitDuDemon(FruitDuDemon),
+    
+    #[serde(rename = "Aliment")]
+    Aliment(Aliment),
+    
+    #[serde(rename = "souslieu")]
+    SousLieu(SousLieu),
+    
+    #[serde(rename = "lieu")]
+    Lieu(Lieu),
+
+    #[serde(rename = "Attaque")]
+    Attaque(Attaque),
+}
+
+// Catégorie d'un objet du monde, utilisée pour l'index par type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TypeObjet {
+    ObjetMobile,
+    ObjetStatique,
+    Pnj,
+    PnjAvecType,
+    Joueur,
+    FruitDuDemon,
+    Aliment,
+    SousLieu,
+    Lieu,
+    Attaque,
+}
+
+impl Objet {
+    pub fn type_objet(&self) -> TypeObjet {
+        match self {
+            Objet::ObjetMobile(_) => TypeObjet::ObjetMobile,
+            Objet::ObjetStatique(_) => TypeObjet::ObjetStatique,
+            Objet::Pnj(_) => TypeObjet::Pnj,
+            Objet::PnjAvecType(_) => TypeObjet::PnjAvecType,
+            Objet::Joueur(_) => TypeObjet::Joueur,
+            Objet::FruitDuDemon(_) => TypeObjet::FruitDuDemon,
+            Objet::Aliment(_) => TypeObjet::Aliment,
+            Objet::SousLieu(_) => TypeObjet::SousLieu,
+            Objet::Lieu(_) => TypeObjet::Lieu,
+            Objet::Attaque(_) => TypeObjet::Attaque,
+        }
+    }
+
+    // Identifiant de l'objet, pour les types qui en ont un
+    pub fn id(&self) -> Option<&str> {
+        match self {
+            Objet::ObjetMobile(o) => Some(&o.id),
+            Objet::ObjetStatique(o) => Some(&o.id),
+            Objet::FruitDuDemon(f) => Some(&f.id),
+            Objet::Aliment(a) => Some(&a.id),
+            Objet::SousLieu(sl) => Some(&sl.id),
+            Objet::Lieu(l) => Some(&l.id),
+            Objet::Attaque(a) => Some(&a.id),
+            Objet::Pnj(_) | Objet::PnjAvecType(_) | Objet::Joueur(_) => None,
+        }
+    }
+
+    // Emplacement (position, sous_position) des objets posés dans un sous-lieu
+    pub fn emplacement(&self) -> Option<(&str, &str)> {
+        match self {
+            Objet::ObjetMobile(o) => Some((&o.position, &o.sous_position)),
+            Objet::ObjetStatique(o) => Some((&o.position, &o.sous_position)),
+            Objet::Pnj(p) => Some((&p.position, &p.sous_position)),
+            Objet::PnjAvecType(p) => Some((&p.pnj.position, &p.pnj.sous_position)),
+            Objet::FruitDuDemon(f) => Some((&f.position, &f.sous_position)),
+            Objet::Aliment(a) => Some((&a.position, &a.sous_position)),
+            Objet::Joueur(_) | Objet::SousLieu(_) | Objet::Lieu(_) | Objet::Attaque(_) => None,
+        }
+    }
+}
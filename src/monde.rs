@@ -0,0 +1,470 @@
+// Magasin indexé des objets du monde et état d'une partie
+
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::modele::*;
+
+// Magasin des objets du monde, indexé une fois au chargement.
+// Chaque ajout, retrait ou déplacement ne met à jour que les entrées des objets concernés.
+#[derive(Debug, Clone, Default)]
+pub struct Monde {
+    objets: Vec<Objet>,
+    par_id: HashMap<(TypeObjet, String), usize>,
+    par_emplacement: HashMap<(String, String), Vec<usize>>,
+    par_type: HashMap<TypeObjet, Vec<usize>>,
+    sous_lieux_par_lieu: HashMap<String, Vec<usize>>,
+    pnj_par_nom: HashMap<(String, String), usize>, // (position, nom en minuscules)
+    pnj_par_lieu: HashMap<String, Vec<usize>>,
+}
+
+// Retirer un index d'une liste d'index, et la liste elle-même si elle devient vide
+fn retirer_index<K: std::hash::Hash + Eq>(index: &mut HashMap<K, Vec<usize>>, cle: &K, i: usize) {
+    if let Some(liste) = index.get_mut(cle) {
+        liste.retain(|&j| j != i);
+        if liste.is_empty() {
+            index.remove(cle);
+        }
+    }
+}
+
+impl Monde {
+    pub fn new(objets: Vec<Objet>) -> Monde {
+        let mut monde = Monde { objets, ..Monde::default() };
+        for i in 0..monde.objets.len() {
+            monde.indexer(i);
+        }
+        monde
+    }
+
+    // Ajouter l'objet à cet index dans tous les index
+    fn indexer(&mut self, i: usize) {
+        let obj = &self.objets[i];
+        let type_objet = obj.type_objet();
+        self.par_type.entry(type_objet).or_default().push(i);
+
+        // En cas de doublon, le premier objet indexé fait foi
+        if let Some(id) = obj.id() {
+            self.par_id.entry((type_objet, id.to_string())).or_insert(i);
+        }
+        if let Some((position, sous_position)) = obj.emplacement() {
+            self.par_emplacement
+                .entry((position.to_string(), sous_position.to_string()))
+                .or_default()
+                .push(i);
+        }
+        match obj {
+            Objet::SousLieu(sl) => {
+                self.sous_lieux_par_lieu.entry(sl.position.clone()).or_default().push(i);
+            }
+            Objet::PnjAvecType(p) => {
+                self.pnj_par_nom
+                    .entry((p.pnj.position.clone(), p.pnj.nom.to_lowercase()))
+                    .or_insert(i);
+                self.pnj_par_lieu.entry(p.pnj.position.clone()).or_default().push(i);
+            }
+            _ => {}
+        }
+    }
+
+    // Retirer l'objet à cet index de tous les index ; un éventuel doublon d'id prend le relais
+    fn desindexer(&mut self, i: usize) {
+        let obj = &self.objets[i];
+        let type_objet = obj.type_objet();
+        retirer_index(&mut self.par_type, &type_objet, i);
+
+        if let Some(id) = obj.id() {
+            let cle = (type_objet, id.to_string());
+            if self.par_id.get(&cle) == Some(&i) {
+                self.par_id.remove(&cle);
+                let doublon = self.par_type.get(&type_objet).into_iter().flatten().copied()
+                    .filter(|&j| self.objets[j].id() == Some(id))
+                    .min();
+                if let Some(j) = doublon {
+                    self.par_id.insert(cle, j);
+                }
+            }
+        }
+        if let Some((position, sous_position)) = obj.emplacement() {
+            retirer_index(&mut self.par_emplacement, &(position.to_string(), sous_position.to_string()), i);
+        }
+        match obj {
+            Objet::SousLieu(sl) => retirer_index(&mut self.sous_lieux_par_lieu, &sl.position, i),
+            Objet::PnjAvecType(p) => {
+                let cle = (p.pnj.position.clone(), p.pnj.nom.to_lowercase());
+                if self.pnj_par_nom.get(&cle) == Some(&i) {
+                    self.pnj_par_nom.remove(&cle);
+                }
+                retirer_index(&mut self.pnj_par_lieu, &p.pnj.position, i);
+            }
+            _ => {}
+        }
+    }
+
+    pub fn objets(&self) -> &[Objet] {
+        &self.objets
+    }
+
+    pub fn get(&self, index: usize) -> Option<&Objet> {
+        self.objets.get(index)
+    }
+
+    // État d'un PNJ (HP, objets reçus…) : il ne fait partie d'aucun index et peut changer librement
+    pub fn type_de_pnj_mut(&mut self, index: usize) -> Option<&mut PnjType> {
+        match self.objets.get_mut(index) {
+            Some(Objet::PnjAvecType(p)) => Some(&mut p.type_de_pnj),
+            _ => None,
+        }
+    }
+
+    // Objets que porte un PNJ
+    pub fn inventaire_pnj_mut(&mut self, index: usize) -> Option<&mut Vec<String>> {
+        match self.objets.get_mut(index) {
+            Some(Objet::PnjAvecType(p)) => Some(&mut p.pnj.inventaire),
+            _ => None,
+        }
+    }
+
+    // Ajouter des exemplaires à un aliment posé dans le monde ; renvoie false si l'index n'est pas un aliment
+    pub fn ajouter_quantite_aliment(&mut self, index: usize, quantite: u32) -> bool {
+        match self.objets.get_mut(index) {
+            Some(Objet::Aliment(a)) => {
+                a.quantite += quantite;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // Remplacer un objet, en ne mettant à jour que ses propres entrées d'index
+    pub fn remplacer(&mut self, index: usize, objet: Objet) {
+        self.desindexer(index);
+        self.objets[index] = objet;
+        self.indexer(index);
+    }
+
+    pub fn index_par_id(&self, type_objet: TypeObjet, id: &str) -> Option<usize> {
+        self.par_id.get(&(type_objet, id.to_string())).copied()
+    }
+
+    pub fn lieu(&self, id: &str) -> Option<&Lieu> {
+        match self.index_par_id(TypeObjet::Lieu, id).map(|i| &self.objets[i]) {
+            Some(Objet::Lieu(l)) => Some(l),
+            _ => None,
+        }
+    }
+
+    pub fn attaque(&self, id: &str) -> Option<&Attaque> {
+        match self.index_par_id(TypeObjet::Attaque, id).map(|i| &self.objets[i]) {
+            Some(Objet::Attaque(a)) => Some(a),
+            _ => None,
+        }
+    }
+
+    pub fn objet_statique(&self, id: &str) -> Option<&ObjetStatique> {
+        match self.index_par_id(TypeObjet::ObjetStatique, id).map(|i| &self.objets[i]) {
+            Some(Objet::ObjetStatique(o)) => Some(o),
+            _ => None,
+        }
+    }
+
+    // Sous-lieux d'un lieu donné
+    pub fn sous_lieux(&self, position: &str) -> impl Iterator<Item = &SousLieu> {
+        self.sous_lieux_par_lieu.get(position).into_iter().flatten().filter_map(|&i| match &self.objets[i] {
+            Objet::SousLieu(sl) => Some(sl),
+            _ => None,
+        })
+    }
+
+    pub fn sous_lieu(&self, position: &str, id: &str) -> Option<&SousLieu> {
+        self.sous_lieux(position).find(|sl| sl.id == id)
+    }
+
+    // Index des objets posés à un emplacement donné
+    pub fn indices_a(&self, position: &str, sous_position: &str) -> Vec<usize> {
+        self.par_emplacement
+            .get(&(position.to_string(), sous_position.to_string()))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn objets_a(&self, position: &str, sous_position: &str) -> impl Iterator<Item = &Objet> {
+        self.par_emplacement
+            .get(&(position.to_string(), sous_position.to_string()))
+            .into_iter()
+            .flatten()
+            .map(|&i| &self.objets[i])
+    }
+
+    pub fn de_type(&self, type_objet: TypeObjet) -> impl Iterator<Item = &Objet> {
+        self.par_type.get(&type_objet).into_iter().flatten().map(|&i| &self.objets[i])
+    }
+
+    // Indices des PNJ d'un lieu, pour les modifier sans parcourir tout le monde
+    pub fn indices_pnj(&self, position: &str) -> Vec<usize> {
+        self.pnj_par_lieu.get(position).cloned().unwrap_or_default()
+    }
+
+    // Index d'un PNJ par son nom (insensible à la casse) dans un lieu
+    pub fn index_pnj(&self, position: &str, nom: &str) -> Option<usize> {
+        self.pnj_par_nom.get(&(position.to_string(), nom.to_lowercase())).copied()
+    }
+
+    pub fn ajouter(&mut self, objet: Objet) {
+        self.objets.push(objet);
+        self.indexer(self.objets.len() - 1);
+    }
+
+    // Retirer un objet : le dernier objet du monde prend sa place, seul son index change
+    pub fn retirer(&mut self, index: usize) -> Objet {
+        let dernier = self.objets.len() - 1;
+        self.desindexer(index);
+        if index != dernier {
+            self.desindexer(dernier);
+        }
+        let objet = self.objets.swap_remove(index);
+        if index != dernier {
+            self.indexer(index);
+        }
+        objet
+    }
+
+    // Retirer plusieurs objets ; ils sont renvoyés dans l'ordre de leurs index
+    pub fn retirer_indices(&mut self, indices: &[usize]) -> Vec<Objet> {
+        let mut indices = indices.to_vec();
+        indices.sort_unstable();
+        indices.dedup();
+        // Du plus grand au plus petit, pour qu'aucun index restant à retirer ne soit déplacé
+        let mut retires: Vec<Objet> = indices.iter().rev().map(|&i| self.retirer(i)).collect();
+        retires.reverse();
+        retires
+    }
+
+    // Changer un objet d'emplacement
+    pub fn deplacer(&mut self, index: usize, position: &str, sous_position: &str) {
+        let (ancienne_position, ancienne_sous_position) = match self.objets.get_mut(index) {
+            Some(Objet::ObjetMobile(o)) => (&mut o.position, &mut o.sous_position),
+            Some(Objet::ObjetStatique(o)) => (&mut o.position, &mut o.sous_position),
+            Some(Objet::Aliment(a)) => (&mut a.position, &mut a.sous_position),
+            Some(Objet::FruitDuDemon(f)) => (&mut f.position, &mut f.sous_position),
+            _ => return,
+        };
+        let ancienne_cle = (
+            std::mem::replace(ancienne_position, position.to_string()),
+            std::mem::replace(ancienne_sous_position, sous_position.to_string()),
+        );
+        retirer_index(&mut self.par_emplacement, &ancienne_cle, index);
+        self.par_emplacement
+            .entry((position.to_string(), sous_position.to_string()))
+            .or_default()
+            .push(index);
+    }
+}
+
+// Difficulté de la partie : elle décide de ce qui arrive quand le joueur tombe à 0 HP
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Difficulte {
+    // Retour au point d'accostage, sans pénalité
+    Facile,
+    // Retour au point d'accostage en perdant ses aliments et la moitié de sa puissance
+    #[default]
+    Normale,
+    // Fin de la partie
+    Difficile,
+}
+
+impl Difficulte {
+    // Lire une difficulté saisie en ligne de commande (facile, normale, difficile)
+    pub fn depuis_nom(nom: &str) -> Option<Difficulte> {
+        match nom.to_lowercase().as_str() {
+            "facile" => Some(Difficulte::Facile),
+            "normale" => Some(Difficulte::Normale),
+            "difficile" => Some(Difficulte::Difficile),
+            _ => None,
+        }
+    }
+}
+
+// État de la partie : le joueur y est stocké une seule fois, à part du reste du monde.
+// Les entrées Objet::Joueur n'existent que dans les fichiers de données et les sauvegardes.
+// Tout le hasard de la partie passe par son générateur, pour pouvoir rejouer une session à l'identique.
+#[derive(Debug, Clone)]
+pub struct EtatJeu {
+    joueur: Joueur,
+    monde: Monde,
+    rng: StdRng,
+    difficulte: Difficulte,
+}
+
+impl EtatJeu {
+    pub fn new(joueur: Joueur, monde: Monde) -> EtatJeu {
+        EtatJeu { joueur, monde, rng: StdRng::from_os_rng(), difficulte: Difficulte::default() }
+    }
+
+    // Construire l'état à partir des objets chargés, en extrayant le joueur
+    pub fn depuis_objets(objets: Vec<Objet>) -> Result<EtatJeu, String> {
+        let mut joueur = None;
+        let mut reste = Vec::with_capacity(objets.len());
+        for obj in objets {
+            match obj {
+                Objet::Joueur(j) if joueur.is_none() => joueur = Some(j),
+                Objet::Joueur(_) => return Err("Plusieurs joueurs trouvés dans le monde.".to_string()),
+                Objet::PnjAvecType(mut p) => {
+                    // Les HP de départ d'un ennemi servent de maximum s'il n'est pas donné
+                    if let PnjType::Ennemi { hp, ref mut hp_max, .. } = p.type_de_pnj {
+                        hp_max.get_or_insert(hp);
+                    }
+                    reste.push(Objet::PnjAvecType(p));
+                }
+                autre => reste.push(autre),
+            }
+        }
+        match joueur {
+            Some(joueur) => Ok(EtatJeu::new(joueur, Monde::new(reste))),
+            None => Err("Aucun joueur trouvé dans le monde.".to_string()),
+        }
+    }
+
+    // Reconstruire la liste complète des objets (pour la sauvegarde)
+    pub fn vers_objets(&self) -> Vec<Objet> {
+        let mut objets = Vec::with_capacity(self.monde.objets().len() + 1);
+        objets.push(Objet::Joueur(self.joueur.clone()));
+        objets.extend(self.monde.objets().iter().cloned());
+        objets
+    }
+
+    pub fn joueur(&self) -> &Joueur {
+        &self.joueur
+    }
+
+    pub fn joueur_mut(&mut self) -> &mut Joueur {
+        &mut self.joueur
+    }
+
+    pub fn monde(&self) -> &Monde {
+        &self.monde
+    }
+
+    pub fn monde_mut(&mut self) -> &mut Monde {
+        &mut self.monde
+    }
+
+    // Accès simultané au joueur et au monde, pour les fonctions qui modifient les deux
+    pub fn joueur_et_monde_mut(&mut self) -> (&mut Joueur, &mut Monde) {
+        (&mut self.joueur, &mut self.monde)
+    }
+
+    pub fn difficulte(&self) -> Difficulte {
+        self.difficulte
+    }
+
+    pub fn regler_difficulte(&mut self, difficulte: Difficulte) {
+        self.difficulte = difficulte;
+    }
+
+    pub fn rng(&mut self) -> &mut StdRng {
+        &mut self.rng
+    }
+
+    // Repartir d'une graine donnée (option --seed ou chargement d'une sauvegarde)
+    pub fn semer(&mut self, graine: u64) {
+        self.rng = StdRng::seed_from_u64(graine);
+    }
+
+    // Tirer une nouvelle graine et repartir de celle-ci. Enregistrée dans la sauvegarde,
+    // elle permet de reprendre la partie avec la même suite de tirages que sans interruption.
+    pub fn nouvelle_graine(&mut self) -> u64 {
+        let graine = self.rng.next_u64();
+        self.semer(graine);
+        graine
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests_communs::*;
+
+    #[test]
+    fn test_etat_jeu_extrait_le_joueur() {
+        let objets = vec![
+            Objet::PnjAvecType(creer_pnj_gentil()),
+            Objet::Joueur(creer_joueur_test()),
+            Objet::Attaque(creer_attaque_test()),
+        ];
+        let jeu = EtatJeu::depuis_objets(objets).expect("un joueur");
+        assert_eq!(jeu.joueur().nom, "Test");
+        assert_eq!(jeu.monde().objets().len(), 2);
+        assert!(!jeu.monde().objets().iter().any(|obj| matches!(obj, Objet::Joueur(_))));
+
+        // Le joueur est réinséré une seule fois pour la sauvegarde
+        let objets = jeu.vers_objets();
+        assert_eq!(objets.iter().filter(|obj| matches!(obj, Objet::Joueur(_))).count(), 1);
+
+        assert!(EtatJeu::depuis_objets(vec![]).is_err());
+        assert!(EtatJeu::depuis_objets(vec![
+            Objet::Joueur(creer_joueur_test()),
+            Objet::Joueur(creer_joueur_test()),
+        ]).is_err());
+    }
+
+    #[test]
+    fn test_hp_max_des_ennemis_complete_au_chargement() {
+        let mut ennemi = creer_pnj_ennemi();
+        if let PnjType::Ennemi { ref mut hp_max, .. } = ennemi.type_de_pnj {
+            *hp_max = None;
+        }
+        let jeu = EtatJeu::depuis_objets(vec![Objet::Joueur(creer_joueur_test()), Objet::PnjAvecType(ennemi)]).unwrap();
+        assert!(matches!(
+            jeu.monde().get(0),
+            Some(Objet::PnjAvecType(PnjAvecType { type_de_pnj: PnjType::Ennemi { hp_max: Some(50), .. }, .. }))
+        ));
+    }
+
+    #[test]
+    fn test_monde_index_par_id_et_emplacement() {
+        let monde = Monde::new(vec![
+            Objet::Lieu(creer_lieu_test("piece1", "", vec![])),
+            Objet::SousLieu(creer_sous_lieu_test("NEALABASTA", "piece1")),
+            Objet::SousLieu(creer_sous_lieu_test("SEALABASTA", "piece1")),
+            Objet::PnjAvecType(creer_pnj_gentil()),
+            Objet::Attaque(creer_attaque_test()),
+        ]);
+
+        assert!(monde.lieu("piece1").is_some());
+        assert!(monde.lieu("piece9").is_none());
+        assert!(monde.attaque("attaque1").is_some());
+        assert_eq!(monde.sous_lieux("piece1").count(), 2);
+        assert!(monde.sous_lieu("piece1", "SEALABASTA").is_some());
+        assert_eq!(monde.index_pnj("piece1", "PNJ Gentil"), Some(3));
+        assert_eq!(monde.de_type(TypeObjet::SousLieu).count(), 2);
+    }
+
+    #[test]
+    fn test_monde_reindexe_apres_retrait_et_deplacement() {
+        let mut monde = Monde::new(vec![
+            Objet::PnjAvecType(creer_pnj_gentil()),
+            Objet::ObjetMobile(creer_bateau_test("piece1", "SEALABASTA")),
+            Objet::Attaque(creer_attaque_test()),
+        ]);
+
+        // Le dernier objet (l'attaque) prend la place du PNJ retiré
+        monde.retirer(0);
+        assert!(monde.index_pnj("piece1", "pnj gentil").is_none());
+        assert!(monde.indices_pnj("piece1").is_empty());
+        assert_eq!(monde.index_par_id(TypeObjet::Attaque, "attaque1"), Some(0));
+        assert!(monde.attaque("attaque1").is_some());
+
+        let bateau = monde.indices_a("piece1", "SEALABASTA")[0];
+        monde.deplacer(bateau, "piece2", "SEWATERSEVEN");
+        assert_eq!(monde.objets_a("piece1", "SEALABASTA").count(), 0);
+        assert_eq!(monde.objets_a("piece2", "SEWATERSEVEN").count(), 1);
+
+        monde.ajouter(Objet::PnjAvecType(creer_pnj_gentil()));
+        assert_eq!(monde.index_pnj("piece1", "PNJ Gentil"), Some(2));
+        assert_eq!(monde.indices_pnj("piece1"), vec![2]);
+        assert_eq!(monde.de_type(TypeObjet::PnjAvecType).count(), 1);
+    }
+}
@@ -0,0 +1,2001 @@
+// Règles du jeu : déplacements, interactions avec les PNJ, combats, objets et fruits du démon
+
+use rand::Rng;
+use std::time::Duration;
+
+use crate::afficher;
+use crate::combat::{
+    choisir_attaque_ennemi, etiquette_elements, lire_action, multiplicateur_elements, resoudre_attaque, ActionCombat, ResultatAttaque,
+    CHANCE_FUITE, ENERGIE_MAX, REGENERATION_ENERGIE,
+};
+use crate::console::Console;
+use crate::effets::{ajouter_effet, ecouler_effets, est_etourdi, terminer_combat};
+use crate::equipement::{description_bonus, equiper, retirer};
+use crate::modele::*;
+use crate::monde::{Difficulte, EtatJeu, Monde};
+use crate::progression::{gagner_xp, xp_ennemi, xp_pour_niveau, XP_QUETE};
+
+// Méthodes utilitaires
+impl PnjAvecType {
+
+    // Méthode principale d'interaction qui va router vers la fonction spécifique
+    pub fn interact_with_player(&mut self, jeu: &mut EtatJeu, console: &mut dyn Console) -> String {
+        match &self.type_de_pnj {
+            PnjType::Ennemi { .. } => self.interact_as_ennemi(jeu, console),
+            PnjType::Gentil { .. } => self.interact_as_gentil(jeu, console),
+            PnjType::Entraineur { .. } => self.interact_as_entraineur(jeu, console),
+        }
+    }
+
+    // Interaction spécifique pour les PNJ ennemis
+    fn interact_as_ennemi(&mut self, jeu: &mut EtatJeu, console: &mut dyn Console) -> String {
+        if self.est_vaincu() {
+            return format!("{} est déjà vaincu.", self.pnj.nom);
+        }
+
+        let mut result = format!("🔥 COMBAT! Vous affrontez {} !", self.pnj.nom);
+
+        // Vérifier si le joueur a les objets requis
+        if let PnjType::Ennemi { ref required_items, .. } = self.type_de_pnj {
+            let has_all_items = required_items.iter().all(|item_id| jeu.joueur().possede(item_id));
+
+            if has_all_items {
+                // Trouver l'index du PNJ actuel dans le monde
+                let pnj_index = jeu.monde().index_pnj(&self.pnj.position, &self.pnj.nom);
+
+                if let Some(i) = pnj_index {
+                    combat(jeu, console, i);
+
+                    // Reprendre l'état du PNJ après le combat (HP, inventaire vidé)
+                    if let Some(Objet::PnjAvecType(pnj)) = jeu.monde().get(i) {
+                        *self = pnj.clone();
+                    }
+
+                    if self.est_vaincu() {
+                        result.push_str("\nVous avez remporté le combat!");
+                    } else {
+                        result.push_str("\nVous n'avez pas vaincu l'ennemi.");
+                    }
+                } else {
+                    result.push_str("\nErreur: PNJ introuvable dans la liste des objets.");
+                }
+            } else {
+                result.push_str("\nDéfaite! Vous n'avez pas l'équipement nécessaire.");
+
+                // Le joueur perd des points de vie
+                let joueur = jeu.joueur_mut();
+                joueur.hp = joueur.hp.saturating_sub(10);
+                result.push_str(&format!("\nVous perdez 10 points de vie. HP restants: {}", joueur.hp));
+            }
+        }
+
+        result
+    }
+
+    // Interaction spécifique pour les PNJ gentils
+    fn interact_as_gentil(&mut self, jeu: &mut EtatJeu, console: &mut dyn Console) -> String {
+        // Immédiatement afficher les messages de base et le dialogue spécial s'il existe
+        afficher!(console, "Vous interagissez avec {} :", self.pnj.nom);
+        afficher!(console, "\"{}\"", self.pnj.description);
+        
+        // Ajouter le dialogue spécial s'il existe
+        if let PnjType::Gentil { dialogue_special: Some(ref dialogue), .. } = self.type_de_pnj {
+            afficher!(console, "Message spécial: \"{}\"", dialogue);
+        }
+
+        // Construire aussi la chaîne de résultat pour le retour de fonction
+        let mut result = format!("Vous interagissez avec {} :\n", self.pnj.nom);
+        result.push_str(&format!("\"{}\"\n", self.pnj.description));
+        
+        // Ajouter le dialogue spécial à la chaîne de résultat
+        if let PnjType::Gentil { dialogue_special: Some(ref dialogue), .. } = self.type_de_pnj {
+            result.push_str(&format!("Message spécial: \"{}\"\n", dialogue));
+        }
+        
+        // Gérer les objets à offrir
+        if !self.pnj.inventaire.is_empty() {
+            let objet_id = &self.pnj.inventaire[0];
+            
+            // Trouver l'objet correspondant
+            let objet_trouve = jeu.monde().objet_statique(objet_id).cloned();
+            
+            if let Some(objet) = objet_trouve {
+                afficher!(console, "\n{} vous propose un objet : {}", self.pnj.nom, objet.nom);
+                afficher!(console, "Description : {}", objet.description);
+                afficher!(console, "\nVoulez-vous le prendre? (o/n)");
+                
+                // Maintenant demander l'entrée utilisateur après avoir affiché tous les messages
+                let reponse = console.lire_ligne().unwrap_or_default();
+                let reponse = reponse.trim().to_lowercase();
+                
+                // Ajouter à la chaîne de résultat
+                result.push_str(&format!("\n{} vous propose un objet : {}\n", self.pnj.nom, objet.nom));
+                result.push_str(&format!("Description : {}\n", objet.description));
+                
+                if reponse == "o" || reponse == "oui" {
+                    // Supprimer l'objet de l'inventaire du PNJ
+                    let objet_id = self.pnj.inventaire.remove(0);
+                    
+                    // Ajouter l'objet à l'inventaire du joueur
+                    let mut objet_final = objet.clone();
+                    objet_final.position = "inventaire".to_string();
+                    jeu.joueur_mut().inventaire.push(ObjetInventaire::ObjetStatique(objet_final));
+
+                    afficher!(console, "→ Objet '{}' ajouté à votre inventaire !", objet.nom);
+                    // Recevoir l'objet d'un allié termine sa quête, sauf s'il ne fait que rendre un cadeau du joueur
+                    let rendu = match self.type_de_pnj {
+                        PnjType::Gentil { ref mut objets_recus, .. } => {
+                            let position = objets_recus.iter().position(|id| *id == objet_id);
+                            position.map(|i| objets_recus.remove(i)).is_some()
+                        }
+                        _ => false,
+                    };
+                    if !rendu {
+                        gagner_xp(jeu.joueur_mut(), XP_QUETE, console);
+                    }
+                    result.push_str(&format!("\n→ Objet '{}' ajouté à votre inventaire !", objet.nom));
+                } else {
+                    afficher!(console, "Vous avez refusé l'objet.");
+                    result.push_str("\nVous avez refusé l'objet.");
+                }
+            } else {
+                afficher!(console, "{} a un objet, mais impossible de le trouver dans le monde.", self.pnj.nom);
+                result.push_str(&format!("\n{} a un objet, mais impossible de le trouver dans le monde.", self.pnj.nom));
+            }
+        } else {
+            afficher!(console, "{} n'a rien à vous offrir.", self.pnj.nom);
+            result.push_str(&format!("\n{} n'a rien à vous offrir.", self.pnj.nom));
+        }
+        
+        result
+    }
+
+    // Interaction spécifique pour les PNJ entraîneurs
+    fn interact_as_entraineur(&mut self, jeu: &mut EtatJeu, console: &mut dyn Console) -> String {
+        // Afficher immédiatement les messages d'introduction
+        if let PnjType::Entraineur {
+            ref competence, bonus_puissance, bonus_hp_max, niveau_requis, puissance_requise, deja_entraine,
+        } = self.type_de_pnj {
+            afficher!(console, "Vous interagissez avec {} :", self.pnj.nom);
+            afficher!(console, "\"{}\"", self.pnj.description);
+            if deja_entraine {
+                afficher!(console, "{} vous a déjà appris tout ce qu'il sait en {}.", self.pnj.nom, competence);
+                return format!("Vous interagissez avec {} :\n\"{}\"\n{} vous a déjà appris tout ce qu'il sait en {}.",
+                               self.pnj.nom, self.pnj.description, self.pnj.nom, competence);
+            }
+            afficher!(console, "{} peut vous entraîner en {} et améliorer votre puissance de {} !", 
+                     self.pnj.nom, competence, bonus_puissance);
+            
+            // Vérifier les prérequis pour l'entraînement : un niveau et, parfois, une puissance minimale
+            let (niveau_joueur, puissance_joueur) = (jeu.joueur().niveau, jeu.joueur().puissance);
+            if niveau_joueur >= niveau_requis && puissance_joueur >= puissance_requise {
+                afficher!(console, "Vous avez les prérequis pour cet entraînement.");
+                afficher!(console, "Voulez-vous vous entraîner? (o/n)");
+                
+                // Construire la chaîne de résultat
+                let mut result = format!("Vous interagissez avec {} :\n", self.pnj.nom);
+                result.push_str(&format!("\"{}\"\n", self.pnj.description));
+                result.push_str(&format!("{} peut vous entraîner en {} et améliorer votre puissance de {} !\n", 
+                                self.pnj.nom, competence, bonus_puissance));
+                result.push_str("Vous avez les prérequis pour cet entraînement.\n");
+                
+                // Demander l'entrée utilisateur après avoir affiché tous les messages
+                let reponse = console.lire_ligne().unwrap_or_default();
+                let reponse = reponse.trim().to_lowercase();
+                
+                if reponse == "o" || reponse == "oui" {
+                    // Augmenter la puissance du joueur, et parfois son endurance
+                    let joueur = jeu.joueur_mut();
+                    joueur.puissance += bonus_puissance;
+
+                    afficher!(console, "Votre puissance augmente de {}! Nouvelle puissance: {}", 
+                             bonus_puissance, joueur.puissance);
+                    result.push_str(&format!("Votre puissance augmente de {}! Nouvelle puissance: {}", 
+                                  bonus_puissance, joueur.puissance));
+                    if bonus_hp_max > 0 {
+                        joueur.hp_max += bonus_hp_max;
+                        joueur.hp += bonus_hp_max;
+                        afficher!(console, "Vos HP max augmentent de {}! HP: {}/{}",
+                                 bonus_hp_max, joueur.hp, joueur.hp_max);
+                        result.push_str(&format!("\nVos HP max augmentent de {}! HP: {}/{}",
+                                      bonus_hp_max, joueur.hp, joueur.hp_max));
+                    }
+                    if let PnjType::Entraineur { deja_entraine, .. } = &mut self.type_de_pnj {
+                        *deja_entraine = true;
+                    }
+                } else {
+                    afficher!(console, "Vous avez refusé l'entraînement.");
+                    result.push_str("Vous avez refusé l'entraînement.");
+                }
+                
+                result
+            } else {
+                let mut prerequis = format!("Niveau requis: {} - Votre niveau: {}", niveau_requis, niveau_joueur);
+                if puissance_requise > 0 {
+                    prerequis.push_str(&format!(" | Puissance requise: {} - Votre puissance: {}",
+                                                puissance_requise, puissance_joueur));
+                }
+                afficher!(console, "Vous n'êtes pas assez fort pour cet entraînement.");
+                afficher!(console, "{}", prerequis);
+                
+                let result = format!("Vous interagissez avec {} :\n", self.pnj.nom);
+                result + &format!("\"{}\"\n", self.pnj.description) 
+                     + &format!("{} peut vous entraîner en {} et améliorer votre puissance de {} !\n", 
+                               self.pnj.nom, competence, bonus_puissance)
+                     + "Vous n'êtes pas assez fort pour cet entraînement.\n"
+                     + &prerequis
+            }
+        } else {
+            afficher!(console, "Erreur: Ce PNJ n'est pas un entraîneur!");
+            "Erreur: Ce PNJ n'est pas un entraîneur!".to_string()
+        }
+    }
+}
+
+pub fn show_objects_at_player_position(jeu: &EtatJeu, console: &mut dyn Console) {
+    let monde = jeu.monde();
+    let pos = &jeu.joueur().position;
+    let sous_pos = &jeu.joueur().sous_position;
+
+    // Afficher le lieu principal
+    if let Some(lieu) = monde.lieu(pos) {
+        afficher!(console, "Vous êtes à : {} - {}", lieu.nom, lieu.id);
+        afficher!(console, "{}", lieu.description);
+        afficher!(console, "Connexions :");
+        for conn in &lieu.connections {
+            // Chercher le nom du lieu de destination
+            let nom_dest = monde.lieu(&conn.destination)
+                .map(|l| l.nom.as_str())
+                .unwrap_or("Lieu inconnu");
+            afficher!(console, "  -> {} vers {} ({})", conn.orientation, nom_dest, conn.destination);
+        }
+    }
+
+    // Afficher le sous-lieu si présent
+    if let Some(sl) = monde.sous_lieu(pos, sous_pos) {
+        afficher!(console, "\nSous-lieu : {} - {}", sl.nom, sl.id);
+        afficher!(console, "{}", sl.description);
+        // Afficher les connexions du sous-lieu
+        if !sl.connections.is_empty() {
+            afficher!(console, "Connexions du sous-lieu :");
+            for conn in &sl.connections {
+                // Chercher le nom du sous-lieu ou lieu de destination
+                let nom_dest = monde.sous_lieu(pos, &conn.destination)
+                    .map(|sousl| sousl.nom.as_str())
+                    .or_else(|| monde.lieu(&conn.destination).map(|lieu| lieu.nom.as_str()))
+                    .unwrap_or("Lieu inconnu");
+                afficher!(console, "  -> {} vers {} ({})", conn.orientation, nom_dest, conn.destination);
+            }
+        } else {
+            afficher!(console, "Aucune connexion depuis ce sous-lieu.");
+        }
+    } else {
+        afficher!(console, "\nAucun sous-lieu spécifique ici.");
+    }
+
+    // Afficher les objets et PNJ du sous-lieu
+    afficher!(console, "\nDans ce sous-lieu, vous trouvez :");
+    let mut found = false;
+    for obj in monde.objets_a(pos, sous_pos) {
+        match obj {
+            Objet::ObjetStatique(o) => {
+                afficher!(console, "  • Objet Statique: {} ({})", o.nom, o.id);
+                found = true;
+            }
+            Objet::ObjetMobile(o) => {
+                afficher!(console, "  • Objet Mobile: {} , {} ({})", o.nom, o.description, o.id);
+                found = true;
+            }
+            Objet::Pnj(p) => {
+                afficher!(console, "  • PNJ: {}", p.nom);
+                found = true;
+            }
+            Objet::PnjAvecType(p) => {
+                // Afficher le PNJ avec son type spécifique
+                let type_description = match &p.type_de_pnj {
+                    PnjType::Gentil { .. } => "amical",
+                    PnjType::Ennemi { .. } => "hostile",
+                    PnjType::Entraineur { .. } => "entraîneur",
+                };
+                afficher!(console, "  • PNJ {}: {} - \"{}\"", type_description, p.pnj.nom, p.pnj.description);
+                found = true;
+            }
+            Objet::FruitDuDemon(f) => {
+                afficher!(console, "  • Fruit du Démon: {} ({})", f.nom, f.pouvoir);
+                found = true;
+            }
+            Objet::Aliment(a) => {
+                afficher!(console, "  • Aliment: {}{}, {} (+{} HP)", a.nom, etiquette_quantite(a.quantite), a.description, a.hp);
+                found = true;
+            }
+            _ => {}
+        }
+    }
+    if !found {
+        afficher!(console, "  Rien d'autre ici.");
+    }
+}
+
+pub fn combat(jeu: &mut EtatJeu, console: &mut dyn Console, pnj_index: usize) {
+    // Get a clone of the PNJ with type
+    let pnj_avec_type = if let Some(Objet::PnjAvecType(p)) = jeu.monde().get(pnj_index) {
+        p.clone()
+    } else {
+        afficher!(console, "PNJ introuvable!");
+        return;
+    };
+    
+    // Extraire les attributs de l'ennemi depuis PnjType
+    let (mut pnj_hp, pnj_puissance, pnj_attaques, comportement, elements_pnj) = match &pnj_avec_type.type_de_pnj {
+        PnjType::Ennemi { hp, puissance, attaques, comportement, elements, .. } => {
+            (*hp, *puissance, attaques.clone(), comportement.clone(), elements.clone())
+        }
+        _ => {
+            afficher!(console, "Ce PNJ n'est pas un ennemi!");
+            return;
+        }
+    };
+    
+    let joueur = jeu.joueur().clone();
+    afficher!(console, "⚔️ COMBAT: {} VS {} ⚔️", joueur.nom, pnj_avec_type.pnj.nom);
+    afficher!(console, "{} - HP: {} | Puissance: {} | Défense: {}", joueur.nom, joueur.hp, joueur.puissance_effective(), joueur.defense());
+    afficher!(console, "{} - HP: {} | Puissance: {}{}", pnj_avec_type.pnj.nom, pnj_hp, pnj_puissance, etiquette_elements(&elements_pnj));
+    
+    // Récupérer les attaques du joueur via son fruit du démon
+    let mut attaques_joueur: Vec<Attaque> = Vec::new();
+    if let Some(fruit) = &joueur.fruit_de_demon {
+        for attaque_id in &fruit.attaque {
+            if let Some(attaque) = jeu.monde().attaque(attaque_id) {
+                attaques_joueur.push(attaque.clone());
+            }
+        }
+    }
+    
+    // Les éléments du fruit du joueur décident de ses faiblesses face aux attaques du PNJ
+    let elements_joueur = joueur.fruit_de_demon.as_ref().map(|f| f.elements.clone()).unwrap_or_default();
+    
+    // Récupérer les attaques du PNJ
+    let mut attaques_pnj: Vec<Attaque> = Vec::new();
+    for attaque_id in &pnj_attaques {
+        if let Some(attaque) = jeu.monde().attaque(attaque_id) {
+            attaques_pnj.push(attaque.clone());
+        }
+    }
+    
+    // Boucle de combat
+    let mut effets_pnj: Vec<EffetStatut> = Vec::new();
+    let mut tour = 0;
+    while pnj_hp > 0 && jeu.joueur().hp > 0 {
+        afficher!(console, "\n--- Tour de combat ---");
+        afficher!(console, "{} - HP: {} | Énergie: {}/{}", joueur.nom, jeu.joueur().hp, jeu.joueur().energie, ENERGIE_MAX);
+        afficher!(console, "{} - HP: {}", pnj_avec_type.pnj.nom, pnj_hp);
+        
+        // Début du tour du joueur : l'étourdissement lui fait passer son tour, puis ses effets s'écoulent
+        let joueur_etourdi = est_etourdi(&jeu.joueur().effets);
+        let j = jeu.joueur_mut();
+        ecouler_effets(&mut j.effets, &mut j.hp, &joueur.nom, true, console);
+        if jeu.joueur().hp == 0 {
+            afficher!(console, "\n💀 Défaite! Vous avez été vaincu par {}!", pnj_avec_type.pnj.nom);
+            break;
+        }
+        
+        let mut en_defense = false;
+        if joueur_etourdi {
+            afficher!(console, "\n💫 {} est étourdi et passe son tour!", joueur.nom);
+        } else {
+            match choisir_action_combat(jeu, console, joueur.fruit_de_demon.as_ref(), &attaques_joueur) {
+                // Plus de réponse possible : le combat s'interrompt là où il en est
+                None => {
+                    afficher!(console, "\nLe combat est interrompu.");
+                    break;
+                }
+                Some(ActionCombat::Attaquer(choix_index)) => {
+                    // Le joueur attaque le PNJ
+                    let attaque = attaques_joueur.get(choix_index);
+                    let (nom_attaque_joueur, puissance_attaque_joueur) = match attaque {
+                        Some(attaque) => {
+                            jeu.joueur_mut().energie -= attaque.cout;
+                            (attaque.nom.as_str(), attaque.puissance)
+                        }
+                        None => ("attaque normale", 0),
+                    };
+                    let puissance_joueur = jeu.joueur().puissance_effective();
+                    let multiplicateur = multiplicateur_elements(attaque.map_or(&[], |a| &a.elements), &elements_pnj);
+                    let resultat = resoudre_attaque(jeu.rng(), puissance_joueur, pnj_puissance, puissance_attaque_joueur)
+                        .multiplier(multiplicateur);
+                    afficher!(console);
+                    annoncer_attaque(console, &joueur.nom, &pnj_avec_type.pnj.nom, nom_attaque_joueur, resultat, multiplicateur);
+                    pnj_hp = pnj_hp.saturating_sub(resultat.degats());
+                    infliger_effet(console, &mut effets_pnj, &pnj_avec_type.pnj.nom, attaque, resultat);
+                    
+                    // Vérifier si le PNJ est vaincu
+                    if pnj_hp == 0 {
+                        afficher!(console, "\n🎉 Victoire! {} a été vaincu!", pnj_avec_type.pnj.nom);
+                        break;
+                    }
+                }
+                Some(ActionCombat::Defendre) => {
+                    afficher!(console, "\n🛡️ {} se met en garde!", joueur.nom);
+                    en_defense = true;
+                }
+                Some(ActionCombat::Manger) => {}
+                Some(ActionCombat::Fuir) => {
+                    if jeu.rng().random_bool(CHANCE_FUITE) {
+                        let joueur = jeu.joueur_mut();
+                        if let Some(precedente) = joueur.sous_position_precedente.take() {
+                            joueur.sous_position = precedente;
+                        }
+                        afficher!(console, "\n🏃 Vous prenez la fuite vers {}!", joueur.sous_position);
+                        break;
+                    }
+                    afficher!(console, "\n{} vous barre la route, impossible de fuir!", pnj_avec_type.pnj.nom);
+                }
+            }
+        }
+        
+        // Tour du PNJ : même déroulement que pour le joueur
+        let pnj_etourdi = est_etourdi(&effets_pnj);
+        ecouler_effets(&mut effets_pnj, &mut pnj_hp, &pnj_avec_type.pnj.nom, true, console);
+        if pnj_hp == 0 {
+            afficher!(console, "\n🎉 Victoire! {} a été vaincu!", pnj_avec_type.pnj.nom);
+            break;
+        }
+        
+        if pnj_etourdi {
+            afficher!(console, "{} est étourdi et ne peut pas contre-attaquer!", pnj_avec_type.pnj.nom);
+        } else {
+            // Le PNJ contre-attaque
+            let attaque = choisir_attaque_ennemi(jeu.rng(), &comportement, &attaques_pnj, pnj_hp, tour);
+            let (nom_attaque_pnj, puissance_attaque_pnj) = match attaque {
+                Some(attaque) => (attaque.nom.as_str(), attaque.puissance),
+                // Si le PNJ n'a pas d'attaques, il utilise une attaque normale
+                None => ("une attaque normale", 0),
+            };
+            let puissance_joueur = jeu.joueur().puissance_effective();
+            let multiplicateur = multiplicateur_elements(attaque.map_or(&[], |a| &a.elements), &elements_joueur);
+            let mut resultat = resoudre_attaque(jeu.rng(), pnj_puissance, puissance_joueur, puissance_attaque_pnj)
+                .multiplier(multiplicateur);
+            if en_defense {
+                resultat = resultat.en_defense();
+            }
+            resultat = resultat.amortir(jeu.joueur().defense());
+            annoncer_attaque(console, &pnj_avec_type.pnj.nom, &joueur.nom, nom_attaque_pnj, resultat, multiplicateur);
+            let j = jeu.joueur_mut();
+            j.hp = j.hp.saturating_sub(resultat.degats());
+            infliger_effet(console, &mut j.effets, &joueur.nom, attaque, resultat);
+        }
+        
+        // Vérifier si le joueur est vaincu
+        if jeu.joueur().hp == 0 {
+            afficher!(console, "\n💀 Défaite! Vous avez été vaincu par {}!", pnj_avec_type.pnj.nom);
+            break;
+        }
+        
+        jeu.joueur_mut().recuperer_energie(REGENERATION_ENERGIE);
+        
+        // Attendre que le joueur appuie sur Entrée pour continuer
+        afficher!(console, "\nAppuyez sur Entrée pour continuer...");
+        console.lire_ligne();
+        tour += 1;
+    }
+    
+    // Les effets en tours de combat ne survivent pas au combat
+    terminer_combat(&mut jeu.joueur_mut().effets);
+    
+    // Check if player won the combat
+    if pnj_hp == 0 {
+        afficher!(console, "Vous avez vaincu {}! Vous récupérez ses objets.", pnj_avec_type.pnj.nom);
+        if let PnjType::Ennemi { hp, hp_max, puissance, .. } = pnj_avec_type.type_de_pnj {
+            gagner_xp(jeu.joueur_mut(), xp_ennemi(hp_max.unwrap_or(hp), puissance), console);
+        }
+        
+        // First, find all object IDs in the PNJ's inventory and corresponding objects
+        let mut objets_a_transferer = Vec::new();
+        for objet_id in &pnj_avec_type.pnj.inventaire {
+            if let Some(mut objet_clone) = jeu.monde().objet_statique(objet_id).cloned() {
+                afficher!(console, "→ Objet '{}' récupéré!", objet_clone.nom);
+                objet_clone.position = "inventaire".to_string();
+                objets_a_transferer.push(objet_clone);
+            }
+        }
+        
+        // Now clear PNJ's inventory and transfer objects to player
+        if let Some(inventaire) = jeu.monde_mut().inventaire_pnj_mut(pnj_index) {
+            inventaire.clear(); // Remove all items from PNJ
+        }
+        
+        let joueur = jeu.joueur_mut();
+        joueur.inventaire.extend(objets_a_transferer.into_iter().map(ObjetInventaire::ObjetStatique));
+
+        // Vérifier si le joueur possède tous les Poneglyphes
+        let poneglyphes = ["poneglyphe1", "poneglyphe2", "poneglyphe3", "poneglyphe4"];
+        if poneglyphes.iter().all(|id| joueur.possede(id)) {
+            // Téléporter directement à piece6 SELAUGHTALE
+            afficher!(console, "Vous avez collecté les 4 Poneglyphes! Un portail mystérieux s'ouvre...");
+            joueur.position = "piece6".to_string();
+            joueur.sous_position = "SELAUGHTALE".to_string();
+            joueur.sous_position_precedente = None;
+            afficher!(console, "Vous êtes téléporté dans un lieu mystérieux!");
+        }
+    }
+
+    // Mettre à jour les HP dans l'objet PNJ original
+    if let Some(PnjType::Ennemi { hp, .. }) = jeu.monde_mut().type_de_pnj_mut(pnj_index) {
+        *hp = pnj_hp;
+    }
+}
+
+// Afficher les actions possibles et demander au joueur d'en choisir une, jusqu'à obtenir un choix valide.
+// Renvoie None quand il n'y a plus de réponse à lire.
+fn choisir_action_combat(
+    jeu: &mut EtatJeu,
+    console: &mut dyn Console,
+    fruit: Option<&FruitDuDemon>,
+    attaques_joueur: &[Attaque],
+) -> Option<ActionCombat> {
+    if let Some(fruit) = fruit {
+        afficher!(console, "\nAttaques disponibles (Fruit: {}):", fruit.nom);
+        
+        if attaques_joueur.is_empty() {
+            afficher!(console, "Aucune attaque disponible avec ce fruit.");
+            afficher!(console, "1. Attaque normale - Puissance: {}", jeu.joueur().puissance_effective());
+        } else {
+            for (i, attaque) in attaques_joueur.iter().enumerate() {
+                afficher!(console, "{}. {}{} - Puissance: {} - Coût: {} - {}", 
+                        i + 1, attaque.nom, etiquette_elements(&attaque.elements), attaque.puissance, attaque.cout, attaque.description);
+            }
+        }
+    } else {
+        afficher!(console, "\nAttaque basique disponible:");
+        afficher!(console, "1. Attaque normale - Puissance: {}", jeu.joueur().puissance_effective());
+    }
+    afficher!(console, "D. Se défendre (dégâts réduits de moitié ce tour)");
+    afficher!(console, "M. Manger un aliment");
+    afficher!(console, "F. Fuir");
+    
+    afficher!(console, "\nChoisissez votre action (numéro d'attaque, D, M ou F):");
+    loop {
+        let saisie = console.lire_ligne()?;
+        match lire_action(&saisie, attaques_joueur.len()) {
+            Some(ActionCombat::Manger) => {
+                // Un aliment qui n'a pas pu être consommé ne coûte pas le tour
+                if consommer_aliment(jeu, console) {
+                    return Some(ActionCombat::Manger);
+                }
+            }
+            Some(ActionCombat::Attaquer(i)) if attaques_joueur.get(i).is_some_and(|a| a.cout > jeu.joueur().energie) => {
+                afficher!(console, "Pas assez d'énergie pour {} (coût: {}, énergie: {})!",
+                        attaques_joueur[i].nom, attaques_joueur[i].cout, jeu.joueur().energie);
+            }
+            Some(ActionCombat::Fuir) if jeu.joueur().sous_position_precedente.is_none() => {
+                afficher!(console, "Impossible de fuir : aucun chemin de retour!");
+            }
+            Some(action) => return Some(action),
+            None => afficher!(console, "Choix invalide!"),
+        }
+        afficher!(console, "Choisissez votre action (numéro d'attaque, D, M ou F):");
+    }
+}
+
+// Appliquer à la cible l'effet de statut d'une attaque qui l'a touchée
+fn infliger_effet(
+    console: &mut dyn Console,
+    effets: &mut Vec<EffetStatut>,
+    cible: &str,
+    attaque: Option<&Attaque>,
+    resultat: ResultatAttaque,
+) {
+    if let (Some(statut), ResultatAttaque::Touchee { .. }) = (attaque.and_then(|a| a.effet.as_ref()), resultat) {
+        afficher!(console, "✨ {} subit : {}", cible, statut);
+        ajouter_effet(effets, statut.clone());
+    }
+}
+
+// Afficher le résultat d'une attaque résolue, et son efficacité selon les éléments
+fn annoncer_attaque(
+    console: &mut dyn Console,
+    attaquant: &str,
+    defenseur: &str,
+    nom_attaque: &str,
+    resultat: ResultatAttaque,
+    multiplicateur: f64,
+) {
+    match resultat {
+        ResultatAttaque::Ratee => {
+            afficher!(console, "{} utilise {} mais rate sa cible!", attaquant, nom_attaque);
+        }
+        ResultatAttaque::Esquivee => {
+            afficher!(console, "{} esquive l'attaque de {}!", defenseur, attaquant);
+        }
+        ResultatAttaque::Touchee { degats, critique } => {
+            if critique {
+                afficher!(console, "💥 Coup critique!");
+            }
+            afficher!(console, "{} utilise {} et inflige {} points de dégâts!", attaquant, nom_attaque, degats);
+            if multiplicateur > 1.0 {
+                afficher!(console, "🎯 C'est très efficace!");
+            } else if multiplicateur < 1.0 {
+                afficher!(console, "Ce n'est pas très efficace...");
+            }
+        }
+    }
+}
+
+// Interagir avec un PNJ du lieu ; renvoie false si aucun PNJ de ce nom n'est là
+pub fn interact(jeu: &mut EtatJeu, console: &mut dyn Console, pnj_name: &str) -> bool {
+    let player_position = jeu.joueur().position.clone();
+
+    // Chercher le PNJ et son index
+    let pnj_index = jeu.monde().index_pnj(&player_position, pnj_name);
+
+    if let Some(i) = pnj_index {
+        if let Some(Objet::PnjAvecType(p)) = jeu.monde().get(i) {
+            // Cloner le PNJ pour interaction
+            let mut pnj_clone = p.clone();
+
+            // Utiliser la méthode d'interaction spécifique au type
+            let result = pnj_clone.interact_with_player(jeu, console);
+            afficher!(console, "{}", result);
+
+            // Mettre à jour le PNJ dans la liste des objets
+            jeu.monde_mut().remplacer(i, Objet::PnjAvecType(pnj_clone));
+            return true;
+        }
+    }
+    
+    afficher!(console, "Vous ne voyez pas {} ici.", pnj_name);
+    false
+}
+
+
+pub fn move_inside(jeu: &mut EtatJeu, console: &mut dyn Console, orientation: &str) -> Result<(), String> {
+    let position = jeu.joueur().position.clone();
+    let sous_position = jeu.joueur().sous_position.clone();
+
+    if let Some(current) = jeu.monde().sous_lieu(&position, &sous_position) {
+        if let Some(conn) = current.connections.iter().find(|c| c.orientation == orientation) {
+            if jeu.monde().sous_lieu(&position, &conn.destination).is_some() {
+                let destination = conn.destination.clone();
+                let joueur = jeu.joueur_mut();
+                joueur.sous_position_precedente = Some(std::mem::replace(&mut joueur.sous_position, destination.clone()));
+                afficher!(console, 
+                    "Le joueur se déplace vers le sous-lieu {} ({})",
+                    destination, orientation
+                );
+                return Ok(());
+            } else {
+                return Err(format!(
+                    "La destination {} n'existe pas dans ce lieu.",
+                    conn.destination
+                ));
+            }
+        } else {
+            return Err(format!("Pas de connexion vers {}", orientation));
+        }
+    }
+
+    Err("Sous-lieu actuel introuvable.".to_string())
+}
+
+
+
+
+// Régénérer les ennemis d'une île, et faire réapparaître ceux qui le peuvent une fois vaincus
+fn remettre_en_forme_ennemis(monde: &mut Monde, lieu: &str) {
+    for index in monde.indices_pnj(lieu) {
+        if let Some(PnjType::Ennemi { hp, hp_max, regeneration, reapparait, .. }) = monde.type_de_pnj_mut(index) {
+            let hp_max = hp_max.unwrap_or(*hp);
+            if *hp == 0 {
+                if *reapparait {
+                    *hp = hp_max;
+                }
+            } else {
+                *hp = (*hp + *regeneration).min(hp_max);
+            }
+        }
+    }
+}
+
+pub fn move_joueur(jeu: &mut EtatJeu, console: &mut dyn Console, direction: &str) -> bool {
+    let (joueur, monde) = jeu.joueur_et_monde_mut();
+
+    // Vérifier si un bateau est présent à la position actuelle du joueur
+    let index_bateau = monde.indices_a(&joueur.position, &joueur.sous_position)
+        .into_iter()
+        .find(|&i| matches!(monde.get(i), Some(Objet::ObjetMobile(o)) if o.nom == "Bateau"));
+
+    let index_bateau = match index_bateau {
+        Some(i) => i,
+        None => {
+            afficher!(console, "Il n'y a pas de bateau ici pour vous déplacer, cherchez le bateau.");
+            return false;
+        }
+    };
+
+    if direction != "N" && direction != "S" && direction != "E" && direction != "O" {
+        afficher!(console, "Direction invalide. Utilisez N, S, E ou O.");
+        return false;
+    }
+
+    // Trouver le lieu actuel et vérifier la connexion
+    let lieu_actuel = match monde.lieu(&joueur.position) {
+        Some(lieu) => lieu,
+        None => {
+            afficher!(console, "Lieu actuel invalide !");
+            return false;
+        }
+    };
+
+    let conn = match lieu_actuel.connections.iter().find(|c| c.orientation == direction) {
+        Some(conn) => conn,
+        None => {
+            afficher!(console, "Aucune connexion dans cette direction !");
+            return false;
+        }
+    };
+
+    let destination_lieu = match monde.lieu(&conn.destination) {
+        Some(lieu) => lieu.clone(),
+        None => {
+            afficher!(console, "Lieu actuel invalide !");
+            return false;
+        }
+    };
+
+    // Vérifie si une clé est requise
+    if !destination_lieu.required_key.is_empty() && !joueur.possede(&destination_lieu.required_key) {
+        afficher!(console, "Vous devez avoir '{}' pour y accéder.", destination_lieu.required_key);
+        return false;
+    }
+
+    // Les ennemis de l'île quittée se remettent de leurs blessures
+    remettre_en_forme_ennemis(monde, &joueur.position);
+
+    // Mise à jour position du joueur
+    joueur.position = destination_lieu.id.clone();
+    joueur.sous_position_precedente = None;
+
+    // Rechercher le premier sous-lieu commençant par "SE" dans la nouvelle position
+    let sous_lieu_se = monde.sous_lieux(&joueur.position)
+        .find(|sl| sl.id.starts_with("SE"))
+        .map(|sl| sl.id.clone());
+
+    if let Some(sous_lieu_se) = sous_lieu_se {
+        joueur.sous_position = sous_lieu_se.clone();
+
+        // Mise à jour de la position du bateau
+        afficher!(console, "Vous utilisez le bateau pour aller vers {}.", destination_lieu.nom);
+        monde.deplacer(index_bateau, &destination_lieu.id, &sous_lieu_se);
+    }
+
+    afficher!(console, "Déplacement vers {}", destination_lieu.nom);
+    true
+}
+
+
+// Issue du ramassage des objets d'un sous-lieu
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Ramassage {
+    // Aucun objet ici, ou choix invalide
+    Rien,
+    Objets,
+    // Le joueur a trouvé le One Piece : fin de la partie
+    OnePiece,
+}
+
+// Ramasser les objets du sous-lieu
+pub fn capture_objets_statiques(jeu: &mut EtatJeu, console: &mut dyn Console) -> Ramassage {
+    let mut objets_disponibles = Vec::new();
+    let joueur = jeu.joueur();
+
+    // Collecter tous les objets dans le sous-lieu du joueur
+    for index in jeu.monde().indices_a(&joueur.position, &joueur.sous_position) {
+        match jeu.monde().get(index) {
+            Some(Objet::ObjetStatique(o)) => {
+                objets_disponibles.push((index, format!("Objet: {}", o.nom)));
+            },
+            Some(Objet::Aliment(a)) => {
+                objets_disponibles.push((index, format!("Aliment: {}{} (+{} HP)", a.nom, etiquette_quantite(a.quantite), a.hp)));
+            },
+            _ => {}
+        }
+    }
+
+    if objets_disponibles.is_empty() {
+        afficher!(console, "Aucun objet à ramasser dans ce sous-lieu.");
+        return Ramassage::Rien;
+    }
+
+    // Afficher les options
+    afficher!(console, "Objets disponibles:");
+    afficher!(console, "0. Tout ramasser");
+    for (i, (_, desc)) in objets_disponibles.iter().enumerate() {
+        afficher!(console, "{}. {}", i + 1, desc);
+    }
+    
+    afficher!(console, "Que voulez-vous ramasser? (0-{})", objets_disponibles.len());
+    let choix = console.lire_ligne().unwrap_or_default();
+    let choix: usize = match choix.trim().parse() {
+        Ok(num) if num <= objets_disponibles.len() => num,
+        _ => {
+            afficher!(console, "Choix invalide. Rien n'a été ramassé.");
+            return Ramassage::Rien;
+        }
+    };
+
+    let indices_a_capturer: Vec<usize> = if choix == 0 {
+        // Ramasser tous les objets
+        objets_disponibles.iter().map(|(index, _)| *index).collect()
+    } else {
+        // Ramasser un seul objet
+        vec![objets_disponibles[choix - 1].0]
+    };
+
+    // Retirer les objets du monde et les convertir en ObjetInventaire
+    let mut objets_a_ajouter = vec![];
+    for obj in jeu.monde_mut().retirer_indices(&indices_a_capturer) {
+        match obj {
+            Objet::ObjetStatique(o) => {
+                afficher!(console, "→ Objet '{}' capturé dans le sous-lieu !", o.nom);
+                objets_a_ajouter.push(ObjetInventaire::ObjetStatique(o));
+            },
+            Objet::Aliment(a) => {
+                afficher!(console, "→ Aliment '{}'{} (+{} HP) capturé dans le sous-lieu !", a.nom, etiquette_quantite(a.quantite), a.hp);
+                objets_a_ajouter.push(ObjetInventaire::Aliment(a));
+            },
+            _ => {}
+        }
+    }
+
+    // Ajouter les objets capturés à l'inventaire du joueur
+    let joueur = jeu.joueur_mut();
+    for objet in objets_a_ajouter {
+        joueur.ajouter_objet(objet);
+    }
+
+    // Vérifier si le joueur a obtenu le One Piece
+    if joueur.possede("onepiece") {
+        afficher!(console, "\n\n🎉🎉🎉 FÉLICITATIONS! 🎉🎉🎉");
+        afficher!(console, "Vous avez découvert le ONE PIECE, le trésor légendaire laissé par Gold Roger!");
+        afficher!(console, "Vous êtes maintenant le ROI DES PIRATES!");
+        afficher!(console, "\nFIN DU JEU");
+        
+        // ASCII Art et pause comme précédemment
+        afficher!(console, "\n");
+        afficher!(console, "     ____    ,____     ____           ____     O  ____     ____     ____ ");
+        afficher!(console, "   /'    )--/'    )  /'    )        /'    )--/' /'    )  /'    )--/'    )");
+        afficher!(console, " /'    /' /'    /' /(___,/'       /'    /' /' /(___,/' /'       /(___,/' ");
+        afficher!(console, "(___,/' /'    /(__(________     /(___,/'  (__(________(___,/   (________ ");
+        afficher!(console, "                              /'                                         ");
+        afficher!(console, "                            /'                                           ");
+        afficher!(console, "                          /'                                             ");
+        afficher!(console, "\n");
+        
+        console.pause(Duration::from_millis(5000));
+        return Ramassage::OnePiece;
+    }
+    Ramassage::Objets
+}
+
+// Manger le fruit du démon du sous-lieu ; renvoie true si le joueur l'a mangé (ou échangé contre le sien)
+pub fn capture_fruit_de_demon(jeu: &mut EtatJeu, console: &mut dyn Console) -> bool {
+    let (joueur, monde) = jeu.joueur_et_monde_mut();
+    // Chercher un fruit du démon dans la même sous_position
+    if let Some((idx, fruit)) = monde.indices_a(&joueur.position, &joueur.sous_position)
+        .into_iter()
+        .find_map(|i| match monde.get(i) {
+            Some(Objet::FruitDuDemon(f)) => Some((i, f.clone())),
+            _ => None,
+        })
+    {
+        afficher!(console, "Un fruit du démon ({}, {}) est trouvé dans ta zone !", fruit.nom, fruit.description);
+        match &joueur.fruit_de_demon {
+            None => {
+                afficher!(console, "Vous n'avez pas de fruit du démon. Voulez-vous le manger ? (o/n)");
+                let reponse = console.lire_ligne().unwrap_or_default();
+                let reponse = reponse.trim().to_lowercase();
+                if reponse == "o" || reponse == "oui" {
+                    joueur.fruit_de_demon = Some(fruit);
+                    monde.retirer(idx);
+                    afficher!(console, "Vous avez mangé le fruit du démon !");
+                    return true;
+                }
+                afficher!(console, "Vous avez ignoré le fruit du démon.");
+            }
+            Some(fruit_actuel) => {
+                afficher!(console, "Vous avez déjà le fruit '{}'. Voulez-vous l'échanger avec '{}' ? (o/n)", fruit_actuel.nom, fruit.nom);
+                let reponse = console.lire_ligne().unwrap_or_default();
+                let reponse = reponse.trim().to_lowercase();
+                if reponse == "o" || reponse == "oui" {
+                    // Remettre l'ancien fruit à la place du nouveau
+                    let mut ancien = fruit_actuel.clone();
+                    ancien.position = joueur.position.clone();
+                    ancien.sous_position = joueur.sous_position.clone();
+                    monde.retirer(idx);
+                    monde.ajouter(Objet::FruitDuDemon(ancien));
+                    joueur.fruit_de_demon = Some(fruit);
+                    afficher!(console, "Vous avez échangé votre fruit du démon !");
+                    return true;
+                }
+                afficher!(console, "Vous gardez votre fruit actuel.");
+            }
+        }
+    } else {
+        afficher!(console, "Aucun fruit du démon trouvé dans votre zone.");
+    }
+    false
+}
+
+pub fn afficher_stats(jeu: &EtatJeu, console: &mut dyn Console) {
+    let joueur = jeu.joueur();
+    afficher!(console, "--- Statistiques du joueur ---");
+    afficher!(console, "Nom         : {}", joueur.nom);
+    match &joueur.fruit_de_demon {
+        Some(fruit) => {
+            afficher!(console, "Fruit       : {} ({}){}", fruit.nom, fruit.pouvoir, etiquette_elements(&fruit.elements));
+            afficher!(console, "Attaques    :");
+            for attaque_id in &fruit.attaque {
+                if let Some(attaque) = jeu.monde().attaque(attaque_id) {
+                    afficher!(console, "  • {}{} (puissance: {}, coût: {}): {}",
+                            attaque.nom, etiquette_elements(&attaque.elements), attaque.puissance, attaque.cout, attaque.description);
+                } else {
+                    afficher!(console, "  • Attaque inconnue: {}", attaque_id);
+                }
+            }
+        }
+        None => afficher!(console, "Fruit       : Aucun"),
+    }
+    afficher!(console, "Niveau      : {} (XP : {}/{})", joueur.niveau, joueur.xp, xp_pour_niveau(joueur.niveau + 1));
+    afficher!(console, "HP       : {}/{}", joueur.hp, joueur.hp_max);
+    afficher!(console, "Puissance : {}", joueur.puissance_effective());
+    afficher!(console, "Énergie     : {}/{}", joueur.energie, ENERGIE_MAX);
+    afficher!(console, "Défense     : {}", joueur.defense());
+    if !joueur.equipement.is_empty() {
+        afficher!(console, "Équipement  :");
+        for objet in &joueur.equipement {
+            if let Some(equipable) = &objet.equipable {
+                afficher!(console, "  • {} : {}{}", equipable.emplacement, objet.nom, description_bonus(equipable));
+            }
+        }
+    }
+    if !joueur.effets.is_empty() {
+        afficher!(console, "Effets      :");
+        for statut in &joueur.effets {
+            afficher!(console, "  • {}", statut);
+        }
+    }
+}
+
+// Afficher l'inventaire et l'équipement, puis proposer d'équiper, de retirer, de jeter ou de donner un objet
+pub fn gerer_inventaire(jeu: &mut EtatJeu, console: &mut dyn Console) {
+    let joueur = jeu.joueur_mut();
+    afficher!(console, "Inventaire :");
+    if joueur.inventaire.is_empty() {
+        afficher!(console, "  (vide)");
+    }
+    for (i, item) in joueur.inventaire.iter().enumerate() {
+        match item {
+            ObjetInventaire::Aliment(a) => {
+                afficher!(console, "  {}. 🍖 Aliment: {}{} (+{} HP)", i + 1, a.nom, etiquette_quantite(a.quantite), a.hp);
+            }
+            ObjetInventaire::ObjetStatique(o) => match &o.equipable {
+                Some(equipable) => afficher!(console, "  {}. 🗡️ {}: {}{}",
+                                             i + 1, equipable.emplacement, o.nom, description_bonus(equipable)),
+                None => afficher!(console, "  {}. 📦 Objet: {}", i + 1, o.nom),
+            },
+        }
+    }
+    if !joueur.equipement.is_empty() {
+        afficher!(console, "Équipement porté :");
+        for objet in &joueur.equipement {
+            if let Some(equipable) = &objet.equipable {
+                afficher!(console, "  • {} : {}{}", equipable.emplacement, objet.nom, description_bonus(equipable));
+            }
+        }
+    }
+    if joueur.inventaire.is_empty() && joueur.equipement.is_empty() {
+        return;
+    }
+
+    afficher!(console, "Numéro d'un objet à équiper, R pour retirer un équipement, J pour jeter un objet, D pour le donner à un PNJ, Entrée pour revenir :");
+    let choix = console.lire_ligne().unwrap_or_default();
+    let choix = choix.trim();
+    if choix.is_empty() {
+        return;
+    }
+    if choix.eq_ignore_ascii_case("j") {
+        jeter_objet(jeu, console);
+        return;
+    }
+    if choix.eq_ignore_ascii_case("d") {
+        donner_objet(jeu, console);
+        return;
+    }
+    if choix.eq_ignore_ascii_case("r") {
+        afficher!(console, "Quel emplacement ? (arme/armure/accessoire)");
+        let saisie = console.lire_ligne().unwrap_or_default();
+        match Emplacement::depuis_nom(&saisie) {
+            Some(emplacement) => {
+                if !retirer(joueur, emplacement, console) {
+                    afficher!(console, "Rien n'est équipé à cet emplacement ({}).", emplacement);
+                }
+            }
+            None => afficher!(console, "Emplacement inconnu."),
+        }
+        return;
+    }
+    match choix.parse::<usize>() {
+        Ok(num) if num >= 1 => {
+            equiper(joueur, num - 1, console);
+        }
+        _ => afficher!(console, "Choix invalide."),
+    }
+}
+
+// Choisir un objet de l'inventaire et combien d'exemplaires de sa pile utiliser
+fn choisir_objet_inventaire(joueur: &Joueur, console: &mut dyn Console, action: &str) -> Option<(usize, u32)> {
+    afficher!(console, "Numéro de l'objet à {} ? (1-{})", action, joueur.inventaire.len());
+    let saisie = console.lire_ligne().unwrap_or_default();
+    let index = match saisie.trim().parse::<usize>() {
+        Ok(num) if num >= 1 && num <= joueur.inventaire.len() => num - 1,
+        _ => {
+            afficher!(console, "Choix invalide.");
+            return None;
+        }
+    };
+    let quantite = demander_quantite(console, joueur.inventaire[index].quantite())?;
+    Some((index, quantite))
+}
+
+// Poser un objet de l'inventaire au sol, dans le sous-lieu du joueur, d'où il pourra être ramassé
+fn jeter_objet(jeu: &mut EtatJeu, console: &mut dyn Console) {
+    let (joueur, monde) = jeu.joueur_et_monde_mut();
+    let Some((index, quantite)) = choisir_objet_inventaire(joueur, console, "jeter") else {
+        afficher!(console, "Rien n'a été jeté.");
+        return;
+    };
+    let Some(objet) = joueur.retirer_objet(index, quantite) else { return };
+    afficher!(console, "🗑️ Vous posez au sol : {}{}", objet.nom(), etiquette_quantite(quantite));
+    deposer_objet(monde, objet, &joueur.position, &joueur.sous_position);
+}
+
+// Remettre un objet d'inventaire dans le monde ; un aliment identique déjà posé au même endroit forme une pile
+fn deposer_objet(monde: &mut Monde, objet: ObjetInventaire, position: &str, sous_position: &str) {
+    match objet {
+        ObjetInventaire::Aliment(aliment) => {
+            let pile = monde.indices_a(position, sous_position).into_iter()
+                .find(|&i| matches!(monde.get(i), Some(Objet::Aliment(a)) if a.id == aliment.id));
+            if pile.is_some_and(|i| monde.ajouter_quantite_aliment(i, aliment.quantite)) {
+                return;
+            }
+            monde.ajouter(Objet::Aliment(Aliment {
+                position: position.to_string(),
+                sous_position: sous_position.to_string(),
+                ..aliment
+            }));
+        }
+        ObjetInventaire::ObjetStatique(o) => placer_objet_statique(monde, o, position, sous_position),
+    }
+}
+
+// Ranger un objet statique dans le monde : le monde n'en garde qu'une entrée par id, déplacée si elle existe
+// déjà (par exemple celle, rangée en "null", d'un objet qu'un PNJ a donné au joueur)
+fn placer_objet_statique(monde: &mut Monde, objet: ObjetStatique, position: &str, sous_position: &str) {
+    let index = monde.index_par_id(TypeObjet::ObjetStatique, &objet.id);
+    let objet = Objet::ObjetStatique(ObjetStatique {
+        position: position.to_string(),
+        sous_position: sous_position.to_string(),
+        ..objet
+    });
+    match index {
+        Some(index) => monde.remplacer(index, objet),
+        None => monde.ajouter(objet),
+    }
+}
+
+// Donner un objet de l'inventaire à un PNJ amical de l'île
+fn donner_objet(jeu: &mut EtatJeu, console: &mut dyn Console) {
+    let Some((index, quantite)) = choisir_objet_inventaire(jeu.joueur(), console, "donner") else {
+        afficher!(console, "Rien n'a été donné.");
+        return;
+    };
+    afficher!(console, "À quel PNJ ?");
+    let nom = console.lire_ligne().unwrap_or_default();
+    let position = jeu.joueur().position.clone();
+    let Some(pnj_index) = jeu.monde().index_pnj(&position, nom.trim()) else {
+        afficher!(console, "Vous ne voyez pas {} ici.", nom.trim());
+        return;
+    };
+    let (joueur, monde) = jeu.joueur_et_monde_mut();
+    let Some(Objet::PnjAvecType(pnj)) = monde.get(pnj_index) else { return };
+    let nom_pnj = pnj.pnj.nom.clone();
+    if !matches!(pnj.type_de_pnj, PnjType::Gentil { .. }) {
+        afficher!(console, "{} refuse votre cadeau.", nom_pnj);
+        return;
+    }
+    let Some(objet) = joueur.retirer_objet(index, quantite) else { return };
+    match objet {
+        ObjetInventaire::Aliment(a) => {
+            afficher!(console, "🎁 {} se régale : {}{}. Merci !", nom_pnj, a.nom, etiquette_quantite(quantite));
+        }
+        ObjetInventaire::ObjetStatique(o) => {
+            // Le PNJ garde l'objet et le rendra au joueur qui le lui redemande
+            afficher!(console, "🎁 {} accepte {} et vous le gardera précieusement.", nom_pnj, o.nom);
+            if let Some(PnjType::Gentil { objets_recus, .. }) = monde.type_de_pnj_mut(pnj_index) {
+                objets_recus.push(o.id.clone());
+            }
+            if let Some(inventaire) = monde.inventaire_pnj_mut(pnj_index) {
+                inventaire.push(o.id.clone());
+            }
+            placer_objet_statique(monde, o, "null", "null");
+        }
+    }
+}
+
+// Demander combien d'exemplaires d'une pile utiliser ; une pile d'un seul objet ne pose pas la question
+fn demander_quantite(console: &mut dyn Console, disponible: u32) -> Option<u32> {
+    if disponible <= 1 {
+        return Some(1);
+    }
+    afficher!(console, "Combien ? (1-{}, Entrée pour 1)", disponible);
+    let saisie = console.lire_ligne().unwrap_or_default();
+    let saisie = saisie.trim();
+    if saisie.is_empty() {
+        return Some(1);
+    }
+    match saisie.parse::<u32>() {
+        Ok(n) if n >= 1 && n <= disponible => Some(n),
+        _ => {
+            afficher!(console, "Quantité invalide.");
+            None
+        }
+    }
+}
+
+// " ×3" pour une pile de plusieurs exemplaires, rien pour un objet seul
+fn etiquette_quantite(quantite: u32) -> String {
+    if quantite > 1 {
+        format!(" ×{}", quantite)
+    } else {
+        String::new()
+    }
+}
+
+// Gérer la mort du joueur quand ses HP sont tombés à 0, selon la difficulté.
+// Renvoie true si la partie est terminée ; sinon le joueur revient au point d'accostage "SE" de l'île.
+pub fn verifier_mort(jeu: &mut EtatJeu, console: &mut dyn Console) -> bool {
+    if jeu.joueur().hp > 0 {
+        return false;
+    }
+    let difficulte = jeu.difficulte();
+    let (joueur, monde) = jeu.joueur_et_monde_mut();
+
+    afficher!(console, "\n☠️ {} s'effondre...", joueur.nom);
+    console.pause(Duration::from_millis(1500));
+    if difficulte == Difficulte::Difficile {
+        afficher!(console, "\n================ GAME OVER ================");
+        afficher!(console, "Votre aventure s'arrête ici, loin du One Piece.");
+        afficher!(console, "===========================================");
+        console.pause(Duration::from_millis(2000));
+        return true;
+    }
+
+    // Retour au point d'accostage de l'île où le joueur est tombé
+    if let Some(accostage) = monde.sous_lieux(&joueur.position).find(|sl| sl.id.starts_with("SE")) {
+        joueur.sous_position = accostage.id.clone();
+    }
+    joueur.sous_position_precedente = None;
+    joueur.hp = joueur.hp_max;
+    joueur.effets.clear();
+    afficher!(console, "Vous reprenez connaissance au point d'accostage ({}).", joueur.sous_position);
+
+    if difficulte == Difficulte::Normale {
+        let perdus: u32 = joueur.inventaire.iter()
+            .filter(|item| matches!(item, ObjetInventaire::Aliment(_)))
+            .map(ObjetInventaire::quantite)
+            .sum();
+        joueur.inventaire.retain(|item| !matches!(item, ObjetInventaire::Aliment(_)));
+        joueur.puissance = joueur.puissance.div_ceil(2);
+        if perdus > 0 {
+            afficher!(console, "🍖 Vos {} aliment(s) ont disparu pendant que vous étiez inconscient.", perdus);
+        }
+        afficher!(console, "💔 Affaibli, votre puissance tombe à {}.", joueur.puissance);
+    }
+    false
+}
+
+// Effets du temps qui passe, appliqués après chaque action du menu de jeu qui fait avancer la partie ;
+// les effets absents de `effets_avant` viennent d'être appliqués par cette action et ne s'usent pas encore
+pub fn fin_de_tour(jeu: &mut EtatJeu, effets_avant: &[EffetStatut], console: &mut dyn Console) {
+    let joueur = jeu.joueur_mut();
+    joueur.recuperer_energie(REGENERATION_ENERGIE);
+    let (mut effets, nouveaux): (Vec<_>, Vec<_>) =
+        std::mem::take(&mut joueur.effets).into_iter().partition(|e| effets_avant.contains(e));
+    ecouler_effets(&mut effets, &mut joueur.hp, &joueur.nom, false, console);
+    effets.extend(nouveaux);
+    joueur.effets = effets;
+}
+
+// Proposer de manger un aliment de l'inventaire ; renvoie true si un aliment a été consommé
+pub fn consommer_aliment(jeu: &mut EtatJeu, console: &mut dyn Console) -> bool {
+    let joueur = jeu.joueur_mut();
+    // Un aliment qui augmente les HP max se mange même quand la santé est au maximum
+    let aliment_fortifiant = joueur.inventaire.iter()
+        .any(|o| matches!(o, ObjetInventaire::Aliment(a) if a.bonus_hp_max > 0));
+    if joueur.hp >= joueur.hp_max && !aliment_fortifiant {
+        afficher!(console, "🛑 Vous avez déjà tous vos HP ({}). Impossible de consommer un aliment !", joueur.hp_max);
+        return false;
+    }
+    
+    // Collecter tous les aliments dans l'inventaire
+    let mut aliments = Vec::new();
+    for (i, item) in joueur.inventaire.iter().enumerate() {
+        if let ObjetInventaire::Aliment(a) = item {
+            aliments.push((i, a));
+        }
+    }
+    
+    if aliments.is_empty() {
+        afficher!(console, "Vous n'avez pas d'aliment à consommer !");
+        return false;
+    }
+    
+    // Afficher les options
+    afficher!(console, "Aliments disponibles:");
+    for (i, (_, a)) in aliments.iter().enumerate() {
+        afficher!(console, "{}. {}{} (+{} HP)", i + 1, a.nom, etiquette_quantite(a.quantite), a.hp);
+    }
+    
+    afficher!(console, "Que voulez-vous consommer? (1-{})", aliments.len());
+    let choix = console.lire_ligne().unwrap_or_default();
+    let choix: usize = match choix.trim().parse() {
+        Ok(num) if num >= 1 && num <= aliments.len() => num,
+        _ => {
+            afficher!(console, "Choix invalide. Rien n'a été consommé.");
+            return false;
+        }
+    };
+    
+    // Consommer l'aliment choisi, plusieurs exemplaires d'un coup si le joueur le souhaite
+    let (index, aliment) = aliments[choix - 1];
+    let Some(quantite) = demander_quantite(console, aliment.quantite) else {
+        afficher!(console, "Rien n'a été consommé.");
+        return false;
+    };
+    let Some(ObjetInventaire::Aliment(aliment)) = joueur.retirer_objet(index, quantite) else {
+        return false;
+    };
+    
+    // Vérifier si c'est du Saké de Wano
+    let est_sake = aliment.nom.contains("Saké");
+    
+    let hp_avant = joueur.hp;
+    joueur.hp_max += aliment.bonus_hp_max * quantite;
+    joueur.hp = (joueur.hp + aliment.hp * quantite).min(joueur.hp_max);
+    let hp_gagne = joueur.hp - hp_avant;
+    
+    afficher!(console, "🍽️ Vous consommez : {}{}", aliment.nom, etiquette_quantite(quantite));
+    if aliment.bonus_hp_max > 0 {
+        afficher!(console, "💗 Vos HP max augmentent de {} ! HP max : {}", aliment.bonus_hp_max * quantite, joueur.hp_max);
+    }
+    afficher!(console, "❤️  Vous regagnez {} HP ! HP actuel : {}", hp_gagne, joueur.hp);
+    if let Some(statut) = aliment.effet {
+        afficher!(console, "✨ Effet : {}", statut);
+        ajouter_effet(&mut joueur.effets, statut);
+    }
+    
+    // Appliquer l'effet d'ivresse si c'est du Saké
+    if est_sake {
+        effet_ivresse(jeu, console);
+    }
+    true
+}
+
+fn effet_ivresse(jeu: &mut EtatJeu, console: &mut dyn Console) {
+    afficher!(console, "\n🍶 Vous buvez le Saké de Wano d'une traite...");
+    console.pause(Duration::from_millis(1000));
+    
+    afficher!(console, "Vous sentez une chaleur se répandre dans tout votre corps...");
+    console.pause(Duration::from_millis(1500));
+    
+    // Effet visuel de vision floue
+    afficher!(console, "\nVoTre ViSioN deViEnT flOuE...");
+    console.pause(Duration::from_millis(800));
+    afficher!(console, "LeS sOns SemBleNt dÉfOrmÉs...");
+    console.pause(Duration::from_millis(800));
+    
+    // Dialogue d'ivresse aléatoire
+    let dialogues = [
+        "JE VAIS DEVENIRRR LE ROI DES PIRATESSSS!!!",
+        "Hé Zoro... t'es mon meilleur ami tu sais...",
+        "Je pourrais... *hic*... battre Kaido les yeux fermés...",
+        "Sanji... fais-moi encore à mangerrrrr...",
+        "Shanks! Rends-moi mon chapeau... ah non, il est là..."
+    ];
+    
+    let dialogue = dialogues[jeu.rng().random_range(0..dialogues.len())];
+    afficher!(console, "\nVous criez soudainement: \"{}\"", dialogue);
+    console.pause(Duration::from_millis(2000));
+    
+    afficher!(console, "\n💪 Vous vous sentez INVINCIBLE!");
+    
+    // Mini-jeu d'équilibre
+    afficher!(console, "\n🌀 Vous titubez... Essayez de garder l'équilibre!");
+    afficher!(console, "Tapez 'stable' rapidement pour ne pas tomber!");
+    
+    // Démarrer un timer
+    let debut = std::time::Instant::now();
+    let input = console.lire_ligne().unwrap_or_default();
+    
+    let temps = debut.elapsed().as_secs_f32();
+    
+    if input.trim().to_lowercase() == "stable" && temps < 5.0 {
+        afficher!(console, "✅ Vous gardez l'équilibre (juste à temps)!");
+    } else {
+        afficher!(console, "❌ Vous trébuchez et tombez face contre terre!");
+        
+        // Petite pénalité
+        let joueur = jeu.joueur_mut();
+        joueur.hp = (joueur.hp as f32 * 0.9) as u32; // 10% dégâts
+        afficher!(console, "Vous perdez quelques HP en tombant. HP actuel: {}", joueur.hp);
+    }
+    
+    afficher!(console, "\n⏱️ L'effet du saké se dissipera dans quelques actions...");
+    console.pause(Duration::from_millis(3000));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests_communs::*;
+    use crate::console::ConsoleMemoire;
+    use crate::monde::Monde;
+
+    #[test]
+    fn test_afficher_stats_sans_fruit() {
+        let jeu = EtatJeu::new(creer_joueur_test(), Monde::new(vec![]));
+        let mut console = ConsoleMemoire::default();
+        afficher_stats(&jeu, &mut console); // Doit afficher "Fruit : Aucun"
+    }
+
+    #[test]
+    fn test_afficher_stats_avec_fruit() {
+        let mut joueur = creer_joueur_test();
+        let fruit = FruitDuDemon {
+            id: "f1".to_string(),
+            nom: "Gomu Gomu".to_string(),
+            description: "Fruit du caoutchouc".to_string(),
+            sous_position: "SL1".to_string(),
+            pouvoir: "Caoutchouc".to_string(),
+            position: "piece1".to_string(),
+            attaque: vec!["a1".to_string()],
+            elements: vec![],
+        };
+        joueur.fruit_de_demon = Some(fruit);
+        let attaque = Attaque {
+            id: "a1".to_string(),
+            nom: "Pistol".to_string(),
+            description: "Coup de poing".to_string(),
+            puissance: 30,
+            cout: 20,
+            effet: None,
+            elements: vec![],
+        };
+        let jeu = EtatJeu::new(joueur, Monde::new(vec![Objet::Attaque(attaque)]));
+        let mut console = ConsoleMemoire::default();
+        afficher_stats(&jeu, &mut console); // Doit afficher l'attaque
+        assert!(console.sortie().contains("Pistol (puissance: 30, coût: 20)"));
+        assert!(console.sortie().contains("Énergie     : 100/100"));
+        assert!(console.sortie().contains("Niveau      : 1 (XP : 0/100)"));
+    }
+
+    #[test]
+    fn test_show_objects_at_player_position_empty() {
+        let jeu = EtatJeu::new(creer_joueur_test(), Monde::new(vec![]));
+        let mut console = ConsoleMemoire::default();
+        show_objects_at_player_position(&jeu, &mut console); // Doit n'afficher rien de spécial
+    }
+
+    #[test]
+    fn test_capture_fruit_de_demon_logic() {
+        // Ce test vérifie la logique sans interaction utilisateur
+        let mut joueur = creer_joueur_test();
+        let fruit = FruitDuDemon {
+            id: "f1".to_string(),
+            nom: "Gomu Gomu".to_string(),
+            description: "Fruit du caoutchouc".to_string(),
+            sous_position: "SL1".to_string(),
+            pouvoir: "Caoutchouc".to_string(),
+            position: "piece1".to_string(),
+            attaque: vec![],
+            elements: vec![],
+        };
+        let mut objets = vec![Objet::FruitDuDemon(fruit.clone())];
+
+        // Simule le cas où le joueur n'a pas de fruit et prend le fruit automatiquement (sans interaction)
+        // Pour tester la logique, on appelle directement l'affectation
+        joueur.fruit_de_demon = Some(fruit.clone());
+        objets.remove(0);
+        assert!(joueur.fruit_de_demon.is_some());
+        assert!(objets.is_empty());
+    }
+
+    // Tests pour la fonction interact
+    #[test]
+    fn test_interact_avec_pnj_inexistant() {
+        let mut jeu = creer_jeu_test(vec![
+            Objet::PnjAvecType(creer_pnj_gentil()),
+        ]);
+        
+        // Tester avec un nom de PNJ qui n'existe pas
+        let mut console = ConsoleMemoire::default();
+        assert!(!interact(&mut jeu, &mut console, "PNJ Inconnu"));
+        assert!(console.sortie().contains("Vous ne voyez pas PNJ Inconnu ici."));
+    }
+
+    #[test]
+    fn test_interact_avec_pnj_existant() {
+        let pnj_gentil = creer_pnj_gentil();
+        
+        let mut jeu = creer_jeu_test(vec![
+            Objet::PnjAvecType(pnj_gentil.clone()),
+        ]);
+        
+        // Interagir avec un PNJ qui existe
+        let mut console = ConsoleMemoire::default();
+        interact(&mut jeu, &mut console, &pnj_gentil.pnj.nom);
+        // Le test passe si la fonction ne panique pas
+    }
+
+    #[test]
+    fn test_interact_avec_pnj_different_position() {
+        let mut pnj_gentil = creer_pnj_gentil();
+        pnj_gentil.pnj.position = "piece2".to_string(); // PNJ dans une position différente
+        
+        let mut jeu = creer_jeu_test(vec![
+            Objet::PnjAvecType(pnj_gentil.clone()),
+        ]);
+        
+        // Tenter d'interagir avec un PNJ qui est dans un lieu différent
+        let mut console = ConsoleMemoire::default();
+        interact(&mut jeu, &mut console, &pnj_gentil.pnj.nom);
+        // Le test passe si la fonction ne panique pas
+    }
+
+    #[test]
+    fn test_combat_avec_pnj_non_ennemi() {
+        let pnj_gentil = creer_pnj_gentil();
+        
+        let mut jeu = creer_jeu_test(vec![
+            Objet::PnjAvecType(pnj_gentil),
+        ]);
+        
+        // Tenter de combattre un PNJ qui n'est pas un ennemi
+        let mut console = ConsoleMemoire::default();
+        combat(&mut jeu, &mut console, 0);
+        // Le test passe si la fonction ne panique pas
+    }
+
+    #[test]
+    fn test_move_joueur_signale_le_changement_d_ile() {
+        let connexion = Connection { orientation: "E".to_string(), destination: "piece2".to_string() };
+        let mut jeu = creer_jeu_test(vec![
+            Objet::Lieu(creer_lieu_test("piece1", "", vec![connexion])),
+            Objet::Lieu(creer_lieu_test("piece2", "", vec![])),
+            Objet::SousLieu(creer_sous_lieu_test("SL1", "piece1")),
+            Objet::SousLieu(creer_sous_lieu_test("SEPIECE2", "piece2")),
+            Objet::ObjetMobile(creer_bateau_test("piece1", "SL1")),
+        ]);
+
+        // Pas de connexion vers le nord : aucun déplacement, donc pas d'autosauvegarde
+        let mut console = ConsoleMemoire::default();
+        assert!(!move_joueur(&mut jeu, &mut console, "N"));
+        assert_eq!(jeu.joueur().position, "piece1");
+
+        assert!(move_joueur(&mut jeu, &mut console, "E"));
+        assert_eq!(jeu.joueur().position, "piece2");
+        assert_eq!(jeu.joueur().sous_position, "SEPIECE2");
+
+        // Le bateau suit le joueur
+        assert!(jeu.monde().objets().iter().any(|obj| {
+            matches!(obj, Objet::ObjetMobile(o) if o.position == "piece2" && o.sous_position == "SEPIECE2")
+        }));
+    }
+
+    #[test]
+    fn test_ennemis_remis_en_forme_quand_le_joueur_quitte_l_ile() {
+        let ennemi = |nom: &str, hp: u32, regeneration: u32, reapparait: bool| {
+            let mut pnj = creer_pnj_ennemi();
+            pnj.pnj.nom = nom.to_string();
+            pnj.type_de_pnj = PnjType::Ennemi {
+                puissance: 5,
+                hp,
+                attaques: vec![],
+                required_items: vec![],
+                comportement: Comportement::Premiere,
+                elements: vec![],
+                hp_max: Some(50),
+                regeneration,
+                reapparait,
+            };
+            Objet::PnjAvecType(pnj)
+        };
+        let connexion = Connection { orientation: "E".to_string(), destination: "piece2".to_string() };
+        let mut jeu = creer_jeu_test(vec![
+            Objet::Lieu(creer_lieu_test("piece1", "", vec![connexion])),
+            Objet::Lieu(creer_lieu_test("piece2", "", vec![])),
+            Objet::SousLieu(creer_sous_lieu_test("SL1", "piece1")),
+            Objet::SousLieu(creer_sous_lieu_test("SEPIECE2", "piece2")),
+            Objet::ObjetMobile(creer_bateau_test("piece1", "SL1")),
+            ennemi("Blessé", 10, 30, false),
+            ennemi("Presque guéri", 45, 30, false),
+            ennemi("Boss vaincu", 0, 30, false),
+            ennemi("Soldat vaincu", 0, 0, true),
+        ]);
+        // Un ennemi de l'île d'arrivée n'est pas concerné
+        let mut garde = ennemi("Garde", 10, 30, false);
+        if let Objet::PnjAvecType(p) = &mut garde {
+            p.pnj.position = "piece2".to_string();
+        }
+        jeu.monde_mut().ajouter(garde);
+
+        assert!(move_joueur(&mut jeu, &mut ConsoleMemoire::default(), "E"));
+
+        let hp_a = |lieu: &str, nom: &str| {
+            let i = jeu.monde().index_pnj(lieu, nom).unwrap();
+            match jeu.monde().get(i) {
+                Some(Objet::PnjAvecType(PnjAvecType { type_de_pnj: PnjType::Ennemi { hp, .. }, .. })) => *hp,
+                _ => panic!("{} devrait être un ennemi", nom),
+            }
+        };
+        let hp = |nom: &str| hp_a("piece1", nom);
+        assert_eq!(hp("Blessé"), 40);
+        assert_eq!(hp("Presque guéri"), 50);
+        assert_eq!(hp("Boss vaincu"), 0);
+        assert_eq!(hp("Soldat vaincu"), 50);
+        assert_eq!(hp_a("piece2", "Garde"), 10);
+    }
+
+    #[test]
+    fn test_ennemi_sans_equipement_blesse_le_joueur() {
+        let mut pnj_ennemi = creer_pnj_ennemi();
+        if let PnjType::Ennemi { ref mut required_items, .. } = pnj_ennemi.type_de_pnj {
+            required_items.push("eau".to_string());
+        }
+        let mut jeu = creer_jeu_test(vec![Objet::PnjAvecType(pnj_ennemi.clone())]);
+
+        let mut console = ConsoleMemoire::default();
+
+        interact(&mut jeu, &mut console, &pnj_ennemi.pnj.nom);
+        assert_eq!(jeu.joueur().hp, 90);
+    }
+
+    #[test]
+    fn test_combat_resultat_hp() {
+        // Créer un joueur avec beaucoup de HP pour assurer la victoire
+        let mut joueur = creer_joueur_test();
+        joueur.puissance = 100; // Joueur très puissant
+        
+        // Créer un ennemi faible
+        let mut pnj_ennemi = creer_pnj_ennemi();
+        if let PnjType::Ennemi { ref mut hp, .. } = pnj_ennemi.type_de_pnj {
+            *hp = 10; // Ennemi avec peu de HP
+        }
+        
+        let attaque = creer_attaque_test();
+        
+        let mut jeu = EtatJeu::new(joueur, Monde::new(vec![
+            Objet::PnjAvecType(pnj_ennemi),
+            Objet::Attaque(attaque),
+        ]));
+        
+        // Simuler un combat où le joueur devrait gagner facilement, en attaquant à chaque tour
+        jeu.semer(1);
+        let mut console = ConsoleMemoire::new(["1", ""].repeat(10));
+        combat(&mut jeu, &mut console, 0);
+        
+        // Vérifier que l'ennemi a bien été vaincu (HP à 0)
+        if let Objet::PnjAvecType(pnj) = &jeu.monde().objets()[0] {
+            if let PnjType::Ennemi { hp, .. } = pnj.type_de_pnj {
+                assert_eq!(hp, 0, "L'ennemi devrait être vaincu (HP à 0)");
+            }
+        }
+        assert!(jeu.joueur().xp > 0, "La victoire devrait rapporter de l'XP");
+    }
+
+    #[test]
+    fn test_combat_choix_invalide_sans_perdre_le_tour() {
+        let mut jeu = creer_jeu_test(vec![
+            Objet::PnjAvecType(creer_pnj_ennemi()),
+            Objet::Attaque(creer_attaque_test()),
+        ]);
+        // Saisie invalide, pas d'aliment à manger, nulle part où fuir : l'ennemi n'attaque jamais
+        let mut console = ConsoleMemoire::new(["x", "M", "F"]);
+        combat(&mut jeu, &mut console, 0);
+
+        assert!(console.sortie().contains("Choix invalide!"));
+        assert!(console.sortie().contains("Impossible de fuir"));
+        assert!(console.sortie().contains("Le combat est interrompu."));
+        assert_eq!(jeu.joueur().hp, 100);
+    }
+
+    #[test]
+    fn test_combat_fuite_vers_le_sous_lieu_precedent() {
+        let mut joueur = creer_joueur_test();
+        joueur.hp = 1000;
+        joueur.sous_position_precedente = Some("SEPIECE1".to_string());
+        let mut jeu = EtatJeu::new(joueur, Monde::new(vec![
+            Objet::PnjAvecType(creer_pnj_ennemi()),
+            Objet::Attaque(creer_attaque_test()),
+        ]));
+        jeu.semer(2);
+        let mut console = ConsoleMemoire::new(["F", ""].repeat(20));
+        combat(&mut jeu, &mut console, 0);
+
+        assert!(console.sortie().contains("Vous prenez la fuite vers SEPIECE1"));
+        assert_eq!(jeu.joueur().sous_position, "SEPIECE1");
+        assert_eq!(jeu.joueur().sous_position_precedente, None);
+        // L'ennemi reste en vie avec ses HP intacts
+        if let Some(Objet::PnjAvecType(pnj)) = jeu.monde().get(0) {
+            assert!(matches!(pnj.type_de_pnj, PnjType::Ennemi { hp: 50, .. }));
+        }
+    }
+
+    #[test]
+    fn test_combat_attaque_limitee_par_l_energie() {
+        let mut joueur = creer_joueur_test();
+        joueur.fruit_de_demon = Some(FruitDuDemon {
+            id: "gomu".to_string(),
+            nom: "Gomu Gomu".to_string(),
+            description: "Fruit du caoutchouc".to_string(),
+            sous_position: "SL1".to_string(),
+            pouvoir: "Caoutchouc".to_string(),
+            position: "piece1".to_string(),
+            attaque: vec!["gatling".to_string()],
+            elements: vec![],
+        });
+        let mut gatling = creer_attaque_test();
+        gatling.id = "gatling".to_string();
+        gatling.cout = 60;
+        let mut pnj_ennemi = creer_pnj_ennemi();
+        if let PnjType::Ennemi { ref mut hp, .. } = pnj_ennemi.type_de_pnj {
+            *hp = 10_000;
+        }
+        let mut jeu = EtatJeu::new(joueur, Monde::new(vec![
+            Objet::PnjAvecType(pnj_ennemi),
+            Objet::Attaque(creer_attaque_test()),
+            Objet::Attaque(gatling),
+        ]));
+        // 100 - 60 + 15 de récupération = 55 : la seconde attaque est refusée
+        let mut console = ConsoleMemoire::new(["1", "", "1"]);
+        combat(&mut jeu, &mut console, 0);
+
+        assert!(console.sortie().contains("Pas assez d'énergie pour Attaque Test (coût: 60, énergie: 55)!"));
+        assert_eq!(jeu.joueur().energie, 55);
+    }
+
+    #[test]
+    fn test_combat_etourdissement_empeche_la_riposte() {
+        let mut joueur = creer_joueur_test();
+        joueur.effets.push(EffetStatut { effet: Effet::Brulure { degats: 5 }, duree: Duree::ToursDeCombat(5) });
+        let mut pnj_ennemi = creer_pnj_ennemi();
+        if let PnjType::Ennemi { ref mut hp, ref mut attaques, .. } = pnj_ennemi.type_de_pnj {
+            *hp = 10_000;
+            *attaques = vec!["assommoir".to_string()];
+        }
+        let mut assommoir = creer_attaque_test();
+        assommoir.id = "assommoir".to_string();
+        assommoir.effet = Some(EffetStatut { effet: Effet::Etourdissement, duree: Duree::ToursDeCombat(1) });
+        let mut jeu = EtatJeu::new(joueur, Monde::new(vec![Objet::PnjAvecType(pnj_ennemi), Objet::Attaque(assommoir)]));
+        jeu.semer(3);
+        // Le joueur se défend, est assommé par la riposte et passe le tour suivant
+        let mut console = ConsoleMemoire::new(["D", ""]);
+        combat(&mut jeu, &mut console, 0);
+
+        assert!(console.sortie().contains("Test subit : étourdissement pendant 1 tour(s) de combat"));
+        assert!(console.sortie().contains("Test est étourdi et passe son tour!"));
+        assert!(console.sortie().contains("Test subit 5 dégâts de brûlure!"));
+        // La brûlure ne dure que le temps du combat
+        assert!(jeu.joueur().effets.is_empty());
+    }
+
+    #[test]
+    fn test_bonus_du_sake_temporaire() {
+        let mut joueur = creer_joueur_test();
+        joueur.hp = 50;
+        joueur.inventaire.push(ObjetInventaire::Aliment(Aliment {
+            id: "sake_wano".to_string(),
+            nom: "Saké de Wano".to_string(),
+            description: "Alcool traditionnel de Wano".to_string(),
+            position: "inventaire".to_string(),
+            sous_position: "inventaire".to_string(),
+            hp: 10,
+            bonus_hp_max: 0,
+            quantite: 1,
+            effet: Some(EffetStatut { effet: Effet::BonusPuissance { bonus: 15 }, duree: Duree::Actions(3) }),
+        }));
+        let mut jeu = creer_jeu_test(vec![]);
+        *jeu.joueur_mut() = joueur;
+        let mut console = ConsoleMemoire::new(["1", "stable"]);
+
+        assert!(consommer_aliment(&mut jeu, &mut console));
+        assert_eq!(jeu.joueur().puissance_effective(), 25);
+        for _ in 0..3 {
+            let effets_avant = jeu.joueur().effets.clone();
+            fin_de_tour(&mut jeu, &effets_avant, &mut console);
+        }
+        assert_eq!(jeu.joueur().puissance, 10);
+        assert_eq!(jeu.joueur().puissance_effective(), 10);
+        assert!(console.sortie().contains("se dissipe"));
+    }
+
+    #[test]
+    fn test_mort_selon_la_difficulte() {
+        let aliment = Aliment {
+            id: "riz".to_string(),
+            nom: "Riz".to_string(),
+            description: "Un bol de riz".to_string(),
+            position: "inventaire".to_string(),
+            sous_position: "inventaire".to_string(),
+            hp: 50,
+            bonus_hp_max: 0,
+            quantite: 1,
+            effet: None,
+        };
+        let partie = |difficulte| {
+            let mut jeu = creer_jeu_test(vec![
+                Objet::SousLieu(creer_sous_lieu_test("NOPIECE1", "piece1")),
+                Objet::SousLieu(creer_sous_lieu_test("SEPIECE1", "piece1")),
+            ]);
+            jeu.regler_difficulte(difficulte);
+            let joueur = jeu.joueur_mut();
+            joueur.hp = 0;
+            joueur.puissance = 25;
+            joueur.sous_position = "NOPIECE1".to_string();
+            joueur.inventaire.push(ObjetInventaire::Aliment(aliment.clone()));
+            let mut console = ConsoleMemoire::default();
+            let fin = verifier_mort(&mut jeu, &mut console);
+            (fin, jeu)
+        };
+
+        let (fin, jeu) = partie(Difficulte::Facile);
+        assert!(!fin);
+        assert_eq!((jeu.joueur().hp, jeu.joueur().puissance), (100, 25));
+        assert_eq!(jeu.joueur().sous_position, "SEPIECE1");
+        assert_eq!(jeu.joueur().inventaire.len(), 1);
+
+        let (fin, jeu) = partie(Difficulte::Normale);
+        assert!(!fin);
+        assert_eq!((jeu.joueur().hp, jeu.joueur().puissance), (100, 13));
+        assert_eq!(jeu.joueur().sous_position, "SEPIECE1");
+        assert!(jeu.joueur().inventaire.is_empty());
+
+        let (fin, _) = partie(Difficulte::Difficile);
+        assert!(fin);
+
+        // Un joueur encore en vie n'est pas concerné
+        let mut jeu = creer_jeu_test(vec![]);
+        assert!(!verifier_mort(&mut jeu, &mut ConsoleMemoire::default()));
+    }
+
+    #[test]
+    fn test_capture_fruit_de_demon_avec_reponse() {
+        let fruit = FruitDuDemon {
+            id: "f1".to_string(),
+            nom: "Gomu Gomu".to_string(),
+            description: "Fruit du caoutchouc".to_string(),
+            sous_position: "SL1".to_string(),
+            pouvoir: "Caoutchouc".to_string(),
+            position: "piece1".to_string(),
+            attaque: vec![],
+            elements: vec![],
+        };
+        let mut jeu = creer_jeu_test(vec![Objet::FruitDuDemon(fruit)]);
+
+        let mut console = ConsoleMemoire::new(["o"]);
+        assert!(capture_fruit_de_demon(&mut jeu, &mut console));
+
+        assert_eq!(jeu.joueur().fruit_de_demon.as_ref().map(|f| f.nom.as_str()), Some("Gomu Gomu"));
+        assert!(jeu.monde().objets().is_empty());
+        assert!(console.sortie().contains("Vous avez mangé le fruit du démon !"));
+    }
+
+    #[test]
+    fn test_ramasser_le_one_piece_termine_la_partie() {
+        let one_piece = ObjetStatique {
+            id: "onepiece".to_string(),
+            nom: "One Piece".to_string(),
+            description: "Le trésor de Gold Roger".to_string(),
+            position: "piece1".to_string(),
+            sous_position: "SL1".to_string(),
+            equipable: None,
+        };
+        let mut jeu = creer_jeu_test(vec![Objet::ObjetStatique(one_piece)]);
+
+        let mut console = ConsoleMemoire::new(["0"]);
+        assert_eq!(capture_objets_statiques(&mut jeu, &mut console), Ramassage::OnePiece);
+        assert!(jeu.joueur().possede("onepiece"));
+        assert!(console.sortie().contains("FIN DU JEU"));
+    }
+
+    #[test]
+    fn test_entrainement_selon_le_niveau() {
+        let mut entraineur = creer_pnj_gentil();
+        entraineur.pnj.nom = "Rayleigh".to_string();
+        entraineur.type_de_pnj = PnjType::Entraineur {
+            competence: "Haki".to_string(),
+            bonus_puissance: 20,
+            bonus_hp_max: 20,
+            niveau_requis: 2,
+            puissance_requise: 0,
+            deja_entraine: false,
+        };
+        let mut jeu = creer_jeu_test(vec![Objet::PnjAvecType(entraineur)]);
+        jeu.joueur_mut().hp = 60;
+
+        // Des HP élevés ne suffisent plus : c'est le niveau qui compte
+        let mut console = ConsoleMemoire::new(["o"]);
+        interact(&mut jeu, &mut console, "Rayleigh");
+        assert_eq!(jeu.joueur().puissance, 10);
+        assert!(console.sortie().contains("Niveau requis: 2 - Votre niveau: 1"));
+
+        jeu.joueur_mut().niveau = 2;
+        let mut console = ConsoleMemoire::new(["o"]);
+        interact(&mut jeu, &mut console, "Rayleigh");
+        assert_eq!(jeu.joueur().puissance, 30);
+        assert_eq!((jeu.joueur().hp, jeu.joueur().hp_max), (80, 120));
+
+        // Un second entraînement n'apporte plus rien
+        let mut console = ConsoleMemoire::new(["o"]);
+        interact(&mut jeu, &mut console, "Rayleigh");
+        assert_eq!(jeu.joueur().puissance, 30);
+        assert_eq!((jeu.joueur().hp, jeu.joueur().hp_max), (80, 120));
+        assert!(console.sortie().contains("Rayleigh vous a déjà appris tout ce qu'il sait en Haki."));
+    }
+
+    #[test]
+    fn test_aliment_fortifiant_a_sante_maximale() {
+        let mut jeu = creer_jeu_test(vec![]);
+        jeu.joueur_mut().inventaire.push(ObjetInventaire::Aliment(Aliment {
+            id: "viande".to_string(),
+            nom: "Viande".to_string(),
+            description: "La viande préférée de Luffy".to_string(),
+            position: "inventaire".to_string(),
+            sous_position: "inventaire".to_string(),
+            hp: 100,
+            bonus_hp_max: 10,
+            quantite: 1,
+            effet: None,
+        }));
+        let mut console = ConsoleMemoire::new(["1"]);
+
+        assert!(consommer_aliment(&mut jeu, &mut console));
+        assert_eq!((jeu.joueur().hp, jeu.joueur().hp_max), (110, 110));
+    }
+
+    #[test]
+    fn test_inventaire_equiper_et_retirer() {
+        let mut jeu = creer_jeu_test(vec![]);
+        jeu.joueur_mut().inventaire.push(ObjetInventaire::ObjetStatique(ObjetStatique {
+            id: "epee".to_string(),
+            nom: "Épée de Whitebeard".to_string(),
+            description: "Une épée légendaire".to_string(),
+            position: "inventaire".to_string(),
+            sous_position: "inventaire".to_string(),
+            equipable: Some(Equipable { emplacement: Emplacement::Arme, puissance: 15, defense: 0 }),
+        }));
+
+        let mut console = ConsoleMemoire::new(["1"]);
+        gerer_inventaire(&mut jeu, &mut console);
+        assert!(jeu.joueur().inventaire.is_empty());
+        assert!(jeu.joueur().possede("epee"));
+        assert_eq!(jeu.joueur().puissance_effective(), 25);
+        assert!(console.sortie().contains("1. 🗡️ Arme: Épée de Whitebeard (+15 puissance)"));
+
+        let mut console = ConsoleMemoire::new(["r", "arme"]);
+        gerer_inventaire(&mut jeu, &mut console);
+        assert!(jeu.joueur().equipement.is_empty());
+        assert_eq!(jeu.joueur().puissance_effective(), 10);
+    }
+
+    #[test]
+    fn test_aliments_empiles_manges_et_jetes_par_quantite() {
+        let riz = |quantite| ObjetInventaire::Aliment(Aliment {
+            id: "riz".to_string(),
+            nom: "Riz".to_string(),
+            description: "Un bol de riz".to_string(),
+            position: "inventaire".to_string(),
+            sous_position: "inventaire".to_string(),
+            hp: 20,
+            bonus_hp_max: 0,
+            quantite,
+            effet: None,
+        });
+        let mut jeu = creer_jeu_test(vec![]);
+        let joueur = jeu.joueur_mut();
+        joueur.hp = 30;
+        joueur.ajouter_objet(riz(2));
+        joueur.ajouter_objet(riz(3));
+        assert_eq!(joueur.inventaire.len(), 1);
+        assert_eq!(joueur.inventaire[0].quantite(), 5);
+
+        let mut console = ConsoleMemoire::new(["1", "2"]);
+        assert!(consommer_aliment(&mut jeu, &mut console));
+        assert_eq!(jeu.joueur().hp, 70);
+        assert_eq!(jeu.joueur().inventaire[0].quantite(), 3);
+        assert!(console.sortie().contains("1. Riz ×5 (+20 HP)"));
+
+        // Les aliments jetés restent au sol, à l'endroit du joueur, et s'empilent
+        let mut console = ConsoleMemoire::new(["j", "1", "2", "j", "1"]);
+        gerer_inventaire(&mut jeu, &mut console);
+        gerer_inventaire(&mut jeu, &mut console);
+        assert!(jeu.joueur().inventaire.is_empty());
+        assert!(console.sortie().contains("🗑️ Vous posez au sol : Riz ×2"));
+        let joueur = jeu.joueur();
+        let au_sol: Vec<&Objet> = jeu.monde().objets_a(&joueur.position, &joueur.sous_position).collect();
+        assert!(matches!(au_sol[..], [Objet::Aliment(Aliment { quantite: 3, .. })]));
+    }
+
+    #[test]
+    fn test_donner_un_objet_a_un_pnj_gentil() {
+        let eau = ObjetStatique {
+            id: "eau".to_string(),
+            nom: "Eau".to_string(),
+            description: "Une gourde d'eau".to_string(),
+            position: "inventaire".to_string(),
+            sous_position: "inventaire".to_string(),
+            equipable: None,
+        };
+        let mut jeu = creer_jeu_test(vec![Objet::PnjAvecType(creer_pnj_gentil()), Objet::PnjAvecType(creer_pnj_ennemi())]);
+        jeu.joueur_mut().inventaire.push(ObjetInventaire::ObjetStatique(eau));
+
+        let mut console = ConsoleMemoire::new(["d", "1", "PNJ Ennemi"]);
+        gerer_inventaire(&mut jeu, &mut console);
+        assert!(jeu.joueur().possede("eau"));
+        assert!(console.sortie().contains("PNJ Ennemi refuse votre cadeau."));
+
+        let mut console = ConsoleMemoire::new(["d", "1", "pnj gentil"]);
+        gerer_inventaire(&mut jeu, &mut console);
+        assert!(!jeu.joueur().possede("eau"));
+
+        // Le PNJ rend l'objet qu'on lui a confié, sans récompense de quête
+        let mut console = ConsoleMemoire::new(["o"]);
+        interact(&mut jeu, &mut console, "PNJ Gentil");
+        assert!(jeu.joueur().possede("eau"));
+        assert_eq!(jeu.joueur().xp, 0);
+
+        // Posé au sol, l'objet rendu par le PNJ reste une seule entrée du monde
+        let mut console = ConsoleMemoire::new(["j", "1"]);
+        gerer_inventaire(&mut jeu, &mut console);
+        let (position, sous_position) = (jeu.joueur().position.clone(), jeu.joueur().sous_position.clone());
+        let entrees: Vec<&ObjetStatique> = jeu.monde().de_type(TypeObjet::ObjetStatique)
+            .filter_map(|o| match o {
+                Objet::ObjetStatique(o) if o.id == "eau" => Some(o),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(entrees.len(), 1);
+        assert_eq!((&entrees[0].position, &entrees[0].sous_position), (&position, &sous_position));
+    }
+
+    #[test]
+    fn test_pnj_gentil_offre_son_objet() {
+        let mut pnj_gentil = creer_pnj_gentil();
+        pnj_gentil.pnj.inventaire = vec!["eau".to_string()];
+        let eau = ObjetStatique {
+            id: "eau".to_string(),
+            nom: "Eau".to_string(),
+            description: "Une gourde d'eau".to_string(),
+            position: "null".to_string(),
+            sous_position: "null".to_string(),
+            equipable: None,
+        };
+        let mut jeu = creer_jeu_test(vec![Objet::PnjAvecType(pnj_gentil), Objet::ObjetStatique(eau)]);
+
+        let mut console = ConsoleMemoire::new(["o"]);
+        interact(&mut jeu, &mut console, "PNJ Gentil");
+
+        assert!(jeu.joueur().possede("eau"));
+        assert!(console.sortie().contains("→ Objet 'Eau' ajouté à votre inventaire !"));
+    }
+}
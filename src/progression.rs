@@ -0,0 +1,84 @@
+// Progression du joueur : points d'expérience et niveaux
+
+use crate::afficher;
+use crate::console::Console;
+use crate::modele::Joueur;
+
+pub const HP_MAX_INITIAL: u32 = 100;
+
+// XP gagnée en dehors des combats
+pub const XP_QUETE: u32 = 30;
+pub const XP_MINI_JEU: u32 = 10;
+
+// Gains à chaque niveau
+pub const BONUS_HP_MAX_PAR_NIVEAU: u32 = 20;
+pub const BONUS_PUISSANCE_PAR_NIVEAU: u32 = 5;
+
+// XP totale nécessaire pour atteindre un niveau : 100 pour le niveau 2, 300 pour le 3, 600 pour le 4…
+pub fn xp_pour_niveau(niveau: u32) -> u32 {
+    50 * niveau * niveau.saturating_sub(1)
+}
+
+// XP gagnée en battant un ennemi, selon sa robustesse et sa puissance
+pub fn xp_ennemi(hp_max: u32, puissance: u32) -> u32 {
+    hp_max / 2 + puissance
+}
+
+// Ajouter de l'XP au joueur et lui faire passer autant de niveaux que nécessaire.
+// Chaque niveau augmente les HP max et la puissance, et rend tous les HP.
+pub fn gagner_xp(joueur: &mut Joueur, xp: u32, console: &mut dyn Console) {
+    joueur.xp += xp;
+    afficher!(console, "✨ +{} XP ({} au total)", xp, joueur.xp);
+    while joueur.xp >= xp_pour_niveau(joueur.niveau + 1) {
+        joueur.niveau += 1;
+        joueur.hp_max += BONUS_HP_MAX_PAR_NIVEAU;
+        joueur.puissance += BONUS_PUISSANCE_PAR_NIVEAU;
+        joueur.hp = joueur.hp_max;
+        afficher!(console, "⭐ Niveau {} ! HP max : {} | Puissance : {} (+{})",
+                 joueur.niveau, joueur.hp_max, joueur.puissance, BONUS_PUISSANCE_PAR_NIVEAU);
+    }
+}
+
+// Récompenser une partie de mini-jeu gagnée : chaque mini-jeu ne rapporte de l'XP qu'une fois par île
+pub fn recompenser_mini_jeu(joueur: &mut Joueur, mini_jeu: &str, console: &mut dyn Console) -> bool {
+    let cle = format!("{}/{}", joueur.position, mini_jeu);
+    if joueur.mini_jeux_recompenses.contains(&cle) {
+        afficher!(console, "Vous avez déjà gagné ce mini-jeu sur cette île : pas d'XP cette fois.");
+        return false;
+    }
+    joueur.mini_jeux_recompenses.push(cle);
+    gagner_xp(joueur, XP_MINI_JEU, console);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::console::ConsoleMemoire;
+    use crate::tests_communs::*;
+
+    #[test]
+    fn test_gagner_xp_fait_monter_de_niveau() {
+        let mut joueur = creer_joueur_test();
+        joueur.hp = 40;
+        let mut console = ConsoleMemoire::default();
+
+        gagner_xp(&mut joueur, 90, &mut console);
+        assert_eq!((joueur.niveau, joueur.hp, joueur.hp_max), (1, 40, 100));
+
+        // 350 XP : niveaux 2 et 3 d'un coup
+        gagner_xp(&mut joueur, 260, &mut console);
+        assert_eq!(joueur.niveau, 3);
+        assert_eq!((joueur.hp, joueur.hp_max), (140, 140));
+        assert_eq!(joueur.puissance, 10 + 2 * BONUS_PUISSANCE_PAR_NIVEAU);
+        assert!(console.sortie().contains("⭐ Niveau 3 !"));
+    }
+
+    #[test]
+    fn test_seuils_de_niveau() {
+        assert_eq!(xp_pour_niveau(1), 0);
+        assert_eq!(xp_pour_niveau(2), 100);
+        assert_eq!(xp_pour_niveau(5), 1000);
+        assert_eq!(xp_ennemi(100, 15), 65);
+    }
+}
@@ -0,0 +1,303 @@
+// Emplacements de sauvegarde : écriture, lecture, liste et suppression des parties
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::afficher;
+use crate::console::Console;
+use crate::modele::*;
+use crate::monde::{Difficulte, EtatJeu, Monde};
+
+// Dossier contenant les emplacements de sauvegarde
+pub const DOSSIER_SAUVEGARDES: &str = "sauvegardes";
+
+// Résumé d'une sauvegarde, affiché dans la liste des emplacements
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct InfosSauvegarde {
+    pub nom_joueur: String,
+    pub lieu: String,
+    pub hp: u32,
+    pub puissance: u32,
+    pub poneglyphes: usize,
+    pub horodatage: u64, // Secondes depuis l'epoch Unix
+}
+
+// Contenu d'une sauvegarde : l'état complet du monde
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Sauvegarde {
+    #[serde(default)]
+    pub infos: InfosSauvegarde,
+    #[serde(default)]
+    pub graine: Option<u64>, // Graine du générateur aléatoire au moment de la sauvegarde
+    #[serde(default)]
+    pub difficulte: Difficulte,
+    pub objets: Vec<Objet>,
+}
+
+fn compter_poneglyphes(joueur: &Joueur) -> usize {
+    joueur.inventaire.iter().filter(|item| {
+        matches!(item, ObjetInventaire::ObjetStatique(o) if o.id.starts_with("poneglyphe"))
+    }).count()
+}
+
+pub fn infos_sauvegarde(jeu: &EtatJeu) -> InfosSauvegarde {
+    let joueur = jeu.joueur();
+    let lieu = jeu.monde().lieu(&joueur.position)
+        .map(|l| l.nom.clone())
+        .unwrap_or_else(|| joueur.position.clone());
+
+    let horodatage = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    InfosSauvegarde {
+        nom_joueur: joueur.nom.clone(),
+        lieu,
+        hp: joueur.hp,
+        puissance: joueur.puissance,
+        poneglyphes: compter_poneglyphes(joueur),
+        horodatage,
+    }
+}
+
+// Formater un horodatage Unix en date UTC lisible (AAAA-MM-JJ HH:MM)
+pub fn formater_horodatage(horodatage: u64) -> String {
+    let jours = (horodatage / 86_400) as i64;
+    let secondes = horodatage % 86_400;
+
+    // Conversion jours -> date civile (algorithme de Howard Hinnant)
+    let z = jours + 719_468;
+    let ere = z.div_euclid(146_097);
+    let jour_ere = z.rem_euclid(146_097);
+    let annee_ere = (jour_ere - jour_ere / 1460 + jour_ere / 36_524 - jour_ere / 146_096) / 365;
+    let jour_annee = jour_ere - (365 * annee_ere + annee_ere / 4 - annee_ere / 100);
+    let mp = (5 * jour_annee + 2) / 153;
+    let jour = jour_annee - (153 * mp + 2) / 5 + 1;
+    let mois = if mp < 10 { mp + 3 } else { mp - 9 };
+    let annee = annee_ere + ere * 400 + if mois <= 2 { 1 } else { 0 };
+
+    format!("{:04}-{:02}-{:02} {:02}:{:02} UTC", annee, mois, jour, secondes / 3600, (secondes % 3600) / 60)
+}
+
+// Chemin du fichier correspondant à un emplacement de sauvegarde
+pub fn chemin_emplacement(dossier: &Path, nom: &str) -> PathBuf {
+    let nom_fichier: String = nom.trim().chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    dossier.join(format!("{}.json", nom_fichier))
+}
+
+pub fn sauvegarder_partie(chemin: &Path, jeu: &mut EtatJeu) -> Result<(), String> {
+    let sauvegarde = Sauvegarde {
+        infos: infos_sauvegarde(jeu),
+        graine: Some(jeu.nouvelle_graine()),
+        difficulte: jeu.difficulte(),
+        objets: jeu.vers_objets(),
+    };
+    let contenu = serde_json::to_string_pretty(&sauvegarde)
+        .map_err(|e| format!("Erreur de sérialisation : {}", e))?;
+    if let Some(dossier) = chemin.parent() {
+        fs::create_dir_all(dossier)
+            .map_err(|e| format!("Impossible de créer {} : {}", dossier.display(), e))?;
+    }
+    // Écrire à côté puis renommer : une interruption en pleine écriture laisse l'ancienne sauvegarde intacte
+    let temporaire = chemin.with_extension("json.tmp");
+    fs::write(&temporaire, contenu)
+        .map_err(|e| format!("Impossible d'écrire {} : {}", temporaire.display(), e))?;
+    fs::rename(&temporaire, chemin)
+        .map_err(|e| format!("Impossible d'écrire {} : {}", chemin.display(), e))
+}
+
+// Sauvegarde automatique aux étapes importantes (changement d'île, boss vaincu)
+pub fn autosauvegarde(chemin: &Path, jeu: &mut EtatJeu, console: &mut dyn Console) {
+    match sauvegarder_partie(chemin, jeu) {
+        Ok(()) => afficher!(console, "💾 Sauvegarde automatique effectuée."),
+        Err(e) => afficher!(console, "⚠️ Échec de la sauvegarde automatique : {}", e),
+    }
+}
+
+pub fn nombre_ennemis_vaincus(monde: &Monde) -> usize {
+    monde.de_type(TypeObjet::PnjAvecType).filter(|obj| {
+        matches!(obj, Objet::PnjAvecType(p) if matches!(p.type_de_pnj, PnjType::Ennemi { .. }) && p.est_vaincu())
+    }).count()
+}
+
+fn lire_sauvegarde(chemin: &Path) -> Result<Sauvegarde, String> {
+    let contenu = fs::read_to_string(chemin)
+        .map_err(|e| format!("Impossible de lire {} : {}", chemin.display(), e))?;
+    serde_json::from_str(&contenu)
+        .map_err(|e| format!("Sauvegarde invalide {} : {}", chemin.display(), e))
+}
+
+pub fn charger_partie(chemin: &Path) -> Result<EtatJeu, String> {
+    let sauvegarde = lire_sauvegarde(chemin)?;
+    let mut jeu = EtatJeu::depuis_objets(sauvegarde.objets)
+        .map_err(|e| format!("Sauvegarde invalide {} : {}", chemin.display(), e))?;
+    if let Some(graine) = sauvegarde.graine {
+        jeu.semer(graine);
+    }
+    jeu.regler_difficulte(sauvegarde.difficulte);
+    Ok(jeu)
+}
+
+// Lister les emplacements de sauvegarde, du plus récent au plus ancien
+pub fn lister_emplacements(dossier: &Path, console: &mut dyn Console) -> Vec<(String, InfosSauvegarde)> {
+    let mut emplacements = Vec::new();
+    let entrees = match fs::read_dir(dossier) {
+        Ok(entrees) => entrees,
+        Err(_) => return emplacements, // Pas encore de dossier de sauvegardes
+    };
+
+    for entree in entrees.flatten() {
+        let chemin = entree.path();
+        // Les fichiers "<emplacement>.json.tmp" laissés par une sauvegarde interrompue sont ignorés
+        if chemin.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        let nom = match chemin.file_stem().and_then(|n| n.to_str()) {
+            Some(nom) => nom.to_string(),
+            None => continue,
+        };
+        match lire_sauvegarde(&chemin) {
+            Ok(sauvegarde) => emplacements.push((nom, sauvegarde.infos)),
+            Err(e) => afficher!(console, "⚠️ {}", e),
+        }
+    }
+
+    emplacements.sort_by(|a, b| b.1.horodatage.cmp(&a.1.horodatage).then_with(|| a.0.cmp(&b.0)));
+    emplacements
+}
+
+pub fn supprimer_emplacement(dossier: &Path, nom: &str) -> Result<(), String> {
+    let chemin = chemin_emplacement(dossier, nom);
+    fs::remove_file(&chemin)
+        .map_err(|e| format!("Impossible de supprimer {} : {}", chemin.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests_communs::*;
+    use crate::console::ConsoleMemoire;
+
+    #[test]
+    fn test_sauvegarde_et_chargement() {
+        let mut joueur = creer_joueur_test();
+        let mut pnj_ennemi = creer_pnj_ennemi();
+        if let PnjType::Ennemi { ref mut hp, .. } = pnj_ennemi.type_de_pnj {
+            *hp = 0;
+        }
+        joueur.hp = 42;
+        joueur.position = "piece2".to_string();
+        let mut jeu = EtatJeu::new(joueur, Monde::new(vec![Objet::PnjAvecType(pnj_ennemi)]));
+
+        let chemin = std::env::temp_dir().join("onepiece_test_sauvegarde.json");
+        sauvegarder_partie(&chemin, &mut jeu).expect("sauvegarde");
+        let jeu_charge = charger_partie(&chemin).expect("chargement");
+        let _ = fs::remove_file(&chemin);
+
+        assert_eq!(jeu_charge.joueur().hp, 42);
+        assert_eq!(jeu_charge.joueur().position, "piece2");
+        assert_eq!(jeu_charge.monde().objets().len(), 1);
+        match &jeu_charge.monde().objets()[0] {
+            Objet::PnjAvecType(p) => assert!(p.est_vaincu()),
+            _ => panic!("Le PNJ devrait être le seul objet du monde"),
+        }
+    }
+
+    #[test]
+    fn test_chargement_sauvegarde_sans_joueur() {
+        let chemin = std::env::temp_dir().join("onepiece_test_sauvegarde_vide.json");
+        fs::write(&chemin, r#"{"objets": []}"#).unwrap();
+        let resultat = charger_partie(&chemin);
+        let _ = fs::remove_file(&chemin);
+        assert!(resultat.is_err());
+    }
+
+    #[test]
+    fn test_formater_horodatage() {
+        assert_eq!(formater_horodatage(0), "1970-01-01 00:00 UTC");
+        assert_eq!(formater_horodatage(1_700_000_000), "2023-11-14 22:13 UTC");
+    }
+
+    #[test]
+    fn test_emplacements_lister_et_supprimer() {
+        let dossier = std::env::temp_dir().join("onepiece_test_emplacements");
+        let _ = fs::remove_dir_all(&dossier);
+
+        let mut joueur = creer_joueur_test();
+        joueur.inventaire.push(ObjetInventaire::ObjetStatique(ObjetStatique {
+            id: "poneglyphe1".to_string(),
+            nom: "Poneglyphe ancien".to_string(),
+            description: "Un Poneglyphe".to_string(),
+            position: "inventaire".to_string(),
+            sous_position: "null".to_string(),
+            equipable: None,
+        }));
+        let lieu = Lieu {
+            id: "piece1".to_string(),
+            nom: "Alabasta".to_string(),
+            description: "Un royaume désertique.".to_string(),
+            connections: vec![],
+            required_key: String::new(),
+        };
+        let mut jeu = EtatJeu::new(joueur, Monde::new(vec![Objet::Lieu(lieu)]));
+
+        sauvegarder_partie(&chemin_emplacement(&dossier, "luffy"), &mut jeu).unwrap();
+        sauvegarder_partie(&chemin_emplacement(&dossier, "zoro"), &mut jeu).unwrap();
+        // Reste d'une sauvegarde interrompue en pleine écriture
+        fs::write(dossier.join("nami.json.tmp"), r#"{"infos": {"nom_joueur": "Na"#).unwrap();
+
+        let mut console = ConsoleMemoire::default();
+        let emplacements = lister_emplacements(&dossier, &mut console);
+        assert_eq!(emplacements.len(), 2);
+        assert!(console.sortie().is_empty());
+        assert!(!dossier.join("luffy.json.tmp").exists());
+        let (_, infos) = emplacements.iter().find(|(nom, _)| nom == "luffy").unwrap();
+        assert_eq!(infos.lieu, "Alabasta");
+        assert_eq!(infos.poneglyphes, 1);
+        assert_eq!(infos.hp, 100);
+
+        supprimer_emplacement(&dossier, "zoro").unwrap();
+        let emplacements = lister_emplacements(&dossier, &mut ConsoleMemoire::default());
+        let _ = fs::remove_dir_all(&dossier);
+        assert_eq!(emplacements.len(), 1);
+        assert_eq!(emplacements[0].0, "luffy");
+    }
+
+    #[test]
+    fn test_nombre_ennemis_vaincus() {
+        let mut vaincu = creer_pnj_ennemi();
+        if let PnjType::Ennemi { ref mut hp, .. } = vaincu.type_de_pnj {
+            *hp = 0;
+        }
+        let objets = vec![
+            Objet::PnjAvecType(creer_pnj_ennemi()),
+            Objet::PnjAvecType(vaincu),
+            Objet::PnjAvecType(creer_pnj_gentil()),
+        ];
+        assert_eq!(nombre_ennemis_vaincus(&Monde::new(objets)), 1);
+    }
+
+    #[test]
+    fn test_chargement_reprend_la_suite_des_tirages() {
+        use rand::RngCore;
+
+        let mut jeu = creer_jeu_test(vec![]);
+        jeu.semer(42);
+        jeu.regler_difficulte(Difficulte::Difficile);
+        let chemin = std::env::temp_dir().join("onepiece_test_sauvegarde_graine.json");
+        sauvegarder_partie(&chemin, &mut jeu).expect("sauvegarde");
+        let mut jeu_charge = charger_partie(&chemin).expect("chargement");
+        let _ = fs::remove_file(&chemin);
+        assert_eq!(jeu_charge.difficulte(), Difficulte::Difficile);
+
+        // La partie chargée tire les mêmes nombres que la partie qui a continué sans interruption
+        let attendus: Vec<u64> = (0..5).map(|_| jeu.rng().next_u64()).collect();
+        let obtenus: Vec<u64> = (0..5).map(|_| jeu_charge.rng().next_u64()).collect();
+        assert_eq!(attendus, obtenus);
+    }
+}
@@ -0,0 +1,110 @@
+// Constructeurs de données partagés par les tests des différents modules
+
+use crate::modele::*;
+use crate::monde::*;
+
+// Fonctions utilitaires pour les tests
+pub(crate) fn creer_joueur_test() -> Joueur {
+    Joueur {
+        nom: "Test".to_string(),
+        fruit_de_demon: None,
+        position: "piece1".to_string(),
+        sous_position: "SL1".to_string(),
+        inventaire: vec![],
+        puissance: 10,
+        hp: 100,
+        sous_position_precedente: None,
+        energie: 100,
+        effets: vec![],
+        xp: 0,
+        niveau: 1,
+        hp_max: 100,
+        equipement: vec![],
+        mini_jeux_recompenses: vec![],
+    }
+}
+
+pub(crate) fn creer_jeu_test(objets: Vec<Objet>) -> EtatJeu {
+    EtatJeu::new(creer_joueur_test(), Monde::new(objets))
+}
+
+pub(crate) fn creer_pnj_gentil() -> PnjAvecType {
+    PnjAvecType {
+        pnj: Pnj {
+            nom: "PNJ Gentil".to_string(),
+            description: "Un PNJ amical".to_string(),
+            position: "piece1".to_string(),
+            sous_position: "SL1".to_string(),
+            inventaire: vec![],
+        },
+        type_de_pnj: PnjType::Gentil {
+            dialogue_special: Some("Bonjour aventurier !".to_string()),
+            objets_recus: vec![],
+        },
+    }
+}
+
+pub(crate) fn creer_pnj_ennemi() -> PnjAvecType {
+    PnjAvecType {
+        pnj: Pnj {
+            nom: "PNJ Ennemi".to_string(),
+            description: "Un PNJ hostile".to_string(),
+            position: "piece1".to_string(),
+            sous_position: "SL1".to_string(),
+            inventaire: vec![],
+        },
+        type_de_pnj: PnjType::Ennemi {
+            puissance: 5,
+            hp: 50,
+            attaques: vec!["attaque1".to_string()],
+            required_items: vec![],
+            comportement: Comportement::Premiere,
+            elements: vec![],
+            hp_max: Some(50),
+            regeneration: 0,
+            reapparait: false,
+        },
+    }
+}
+
+pub(crate) fn creer_attaque_test() -> Attaque {
+    Attaque {
+        id: "attaque1".to_string(),
+        nom: "Attaque Test".to_string(),
+        description: "Une attaque pour les tests".to_string(),
+        puissance: 20,
+        cout: 0,
+        effet: None,
+        elements: vec![],
+    }
+}
+
+pub(crate) fn creer_lieu_test(id: &str, required_key: &str, connections: Vec<Connection>) -> Lieu {
+    Lieu {
+        id: id.to_string(),
+        nom: format!("Lieu {}", id),
+        description: "Un lieu de test".to_string(),
+        connections,
+        required_key: required_key.to_string(),
+    }
+}
+
+pub(crate) fn creer_sous_lieu_test(id: &str, position: &str) -> SousLieu {
+    SousLieu {
+        id: id.to_string(),
+        nom: format!("Sous-lieu {}", id),
+        position: position.to_string(),
+        description: "Un sous-lieu de test".to_string(),
+        connections: vec![],
+    }
+}
+
+pub(crate) fn creer_bateau_test(position: &str, sous_position: &str) -> ObjetMobile {
+    ObjetMobile {
+        id: "objet1".to_string(),
+        nom: "Bateau".to_string(),
+        description: "vogue Merry".to_string(),
+        position: position.to_string(),
+        sous_position: sous_position.to_string(),
+    }
+}